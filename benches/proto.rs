@@ -0,0 +1,64 @@
+//! Compares `ProtoSerialize::to_proto_bytes`, which clones the entry fields, with
+//! `ProtoSerialize::into_proto_bytes`, which moves them.
+//!
+//! Run with `cargo bench --features proto --bench proto`.
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use pragma_common::{
+    entries::{OrderbookData, OrderbookEntry, OrderbookUpdateType, PriceEntry},
+    web3::Chain,
+    InstrumentType, Pair, ProtoSerialize,
+};
+
+fn price_entry() -> PriceEntry {
+    PriceEntry {
+        source: "BINANCE".to_string(),
+        chain: Some(Chain::Starknet),
+        pair: Pair::from_currencies("BTC", "USD"),
+        timestamp_ms: 1_700_000_000_000,
+        price: 12_000,
+        volume: 42,
+        expiration_timestamp: None,
+        instrument_type: InstrumentType::Spot,
+        received_timestamp_ms: 1_700_000_000_001,
+        decimals: Some(2),
+    }
+}
+
+fn orderbook_entry() -> OrderbookEntry {
+    let levels = |start: f64| (0..100).map(|i| (start + f64::from(i), 1.0)).collect();
+    OrderbookEntry {
+        source: "BINANCE".to_string(),
+        instrument_type: InstrumentType::Perp,
+        pair: Pair::from_currencies("ETH", "USD"),
+        r#type: OrderbookUpdateType::Snapshot,
+        data: OrderbookData {
+            update_id: 42,
+            bids: levels(1_000.0),
+            asks: levels(1_100.0),
+        },
+        timestamp_ms: 1_700_000_000_000,
+        received_timestamp_ms: 1_700_000_000_001,
+    }
+}
+
+fn bench_entry<T: ProtoSerialize + Clone>(c: &mut Criterion, name: &str, entry: &T) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("to_proto_bytes", |b| b.iter(|| entry.to_proto_bytes()));
+    // The entry is cloned outside of the measurement, as a producer owns it already
+    group.bench_function("into_proto_bytes", |b| {
+        b.iter_batched(
+            || entry.clone(),
+            ProtoSerialize::into_proto_bytes,
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_proto(c: &mut Criterion) {
+    bench_entry(c, "price_entry", &price_entry());
+    bench_entry(c, "orderbook_entry", &orderbook_entry());
+}
+
+criterion_group!(benches, bench_proto);
+criterion_main!(benches);
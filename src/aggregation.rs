@@ -1,10 +1,15 @@
-#[derive(Default, Debug, Clone, Copy)]
+use crate::entries::price::PriceEntry;
+
+/// Parsed & deserialized case-insensitively, displayed & serialized in lowercase,
+/// e.g `"median"`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumString, strum::Display)]
+#[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[cfg_attr(
     feature = "borsh",
     derive(borsh::BorshSerialize, borsh::BorshDeserialize)
 )]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize,))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AggregationMode {
     #[cfg_attr(feature = "serde", serde(rename = "median"))]
     Median,
@@ -13,11 +18,327 @@ pub enum AggregationMode {
     Twap,
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AggregationMode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| serde::de::Error::custom(format!("Unknown aggregation mode: {s}")))
+    }
+}
+
 impl AggregationMode {
+    pub const ALL: [Self; 2] = [Self::Median, Self::Twap];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Median => "median",
             Self::Twap => "twap",
         }
     }
+
+    /// Aggregates the prices of `entries`, returns `None` if there are no entries
+    ///
+    /// - `Median`: median price, the mean of the two middle prices for an even count.
+    /// - `Twap`: entries are sorted by `timestamp_ms` and each price is weighted by the
+    ///   gap (in ms) to the next sample. The last sample is weighted by `tail_weight_ms`.
+    ///   Samples with a zero gap (duplicated timestamps) are skipped, so only the last
+    ///   sample of a timestamp counts. If no weight remains (e.g a single sample with a
+    ///   zero tail weight), the latest price is returned. Returns `None` if the weighted
+    ///   sum overflows a `u128`.
+    pub fn aggregate(&self, entries: &[PriceEntry], tail_weight_ms: u64) -> Option<u128> {
+        match self {
+            Self::Median => median(entries),
+            Self::Twap => twap(entries, tail_weight_ms),
+        }
+    }
+
+    /// Same as [`AggregationMode::aggregate`], but first drops the entries whose price
+    /// deviates from the median by more than `max_deviation_pct` percent (e.g `5.0` = 5%).
+    ///
+    /// Returns the aggregate of the remaining entries along with the sources of the
+    /// rejected ones, `None` if there are no entries left.
+    pub fn aggregate_filtered(
+        &self,
+        entries: &[PriceEntry],
+        tail_weight_ms: u64,
+        max_deviation_pct: f64,
+    ) -> Option<(u128, Vec<String>)> {
+        let median = median(entries)? as f64;
+
+        let (kept, rejected): (Vec<&PriceEntry>, Vec<&PriceEntry>) =
+            entries.iter().partition(|entry| {
+                let deviation = (entry.price as f64 - median).abs();
+                deviation == 0.0 || deviation / median * 100.0 <= max_deviation_pct
+            });
+
+        let kept: Vec<PriceEntry> = kept.into_iter().cloned().collect();
+        let aggregated = self.aggregate(&kept, tail_weight_ms)?;
+        let rejected_sources = rejected
+            .into_iter()
+            .map(|entry| entry.source.clone())
+            .collect();
+        Some((aggregated, rejected_sources))
+    }
+}
+
+/// Exponential moving average of a price feed.
+///
+/// `alpha` is the smoothing factor in `(0, 1]`: the weight given to each new price,
+/// so higher values track the feed closer and `1.0` always returns the last price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ema {
+    pub alpha: f64,
+    /// Current average, `None` until the first update
+    pub value: Option<f64>,
+}
+
+impl Ema {
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in `(0, 1]`.
+    pub fn new(alpha: f64) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "Ema alpha must be in (0, 1], got {alpha}"
+        );
+        Self { alpha, value: None }
+    }
+
+    /// Builds an EMA over the prices of `entries`, taken in timestamp order and
+    /// scaled down by `decimals`.
+    pub fn from_entries(alpha: f64, entries: &[PriceEntry], decimals: u32) -> Self {
+        let mut sorted: Vec<&PriceEntry> = entries.iter().collect();
+        sorted.sort_by_key(|entry| entry.timestamp_ms);
+
+        let scale = 10_f64.powi(decimals as i32);
+        let mut ema = Self::new(alpha);
+        for entry in sorted {
+            ema.update(entry.price as f64 / scale);
+        }
+        ema
+    }
+
+    /// Feeds a new price and returns the updated average. The first price
+    /// initializes the average.
+    pub fn update(&mut self, price: f64) -> f64 {
+        let value = match self.value {
+            Some(value) => self.alpha * price + (1.0 - self.alpha) * value,
+            None => price,
+        };
+        self.value = Some(value);
+        value
+    }
+}
+
+fn median(entries: &[PriceEntry]) -> Option<u128> {
+    let mut prices: Vec<u128> = entries.iter().map(|entry| entry.price).collect();
+    prices.sort_unstable();
+
+    let mid = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(prices[mid]),
+        _ => {
+            let (low, high) = (prices[mid - 1], prices[mid]);
+            Some(low / 2 + high / 2 + (low % 2 + high % 2) / 2)
+        }
+    }
+}
+
+fn twap(entries: &[PriceEntry], tail_weight_ms: u64) -> Option<u128> {
+    let mut sorted: Vec<&PriceEntry> = entries.iter().collect();
+    sorted.sort_by_key(|entry| entry.timestamp_ms);
+    let latest = sorted.last()?;
+
+    let mut weighted_sum: u128 = 0;
+    let mut total_weight: u128 = 0;
+    for (i, entry) in sorted.iter().enumerate() {
+        let weight = match sorted.get(i + 1) {
+            Some(next) => match u128::try_from(next.timestamp_ms - entry.timestamp_ms) {
+                Ok(gap) if gap > 0 => gap,
+                _ => continue,
+            },
+            None => u128::from(tail_weight_ms),
+        };
+        weighted_sum = weighted_sum.checked_add(entry.price.checked_mul(weight)?)?;
+        total_weight = total_weight.checked_add(weight)?;
+    }
+
+    if total_weight == 0 {
+        return Some(latest.price);
+    }
+    Some(weighted_sum / total_weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instrument_type::InstrumentType, pair::Pair};
+
+    fn entry(timestamp_ms: i64, price: u128) -> PriceEntry {
+        PriceEntry {
+            source: "TEST".to_string(),
+            chain: None,
+            pair: Pair::from_currencies("BTC", "USD"),
+            timestamp_ms,
+            price,
+            volume: 0,
+            expiration_timestamp: None,
+            instrument_type: InstrumentType::Spot,
+            received_timestamp_ms: timestamp_ms,
+            decimals: None,
+        }
+    }
+
+    fn sourced(source: &str, price: u128) -> PriceEntry {
+        PriceEntry {
+            source: source.to_string(),
+            ..entry(1_000, price)
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(AggregationMode::Median, "median")]
+    #[case(AggregationMode::Twap, "twap")]
+    fn test_mode_roundtrip(#[case] mode: AggregationMode, #[case] s: &str) {
+        assert_eq!(mode.to_string(), s);
+        assert_eq!(mode.as_str(), s);
+        assert_eq!(s.parse::<AggregationMode>().unwrap(), mode);
+        assert_eq!(s.to_uppercase().parse::<AggregationMode>().unwrap(), mode);
+        assert!(AggregationMode::ALL.contains(&mode));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&mode).unwrap();
+            assert_eq!(json, format!("\"{s}\""));
+            assert_eq!(
+                serde_json::from_str::<AggregationMode>(&json).unwrap(),
+                mode
+            );
+            assert_eq!(
+                serde_json::from_str::<AggregationMode>(&json.to_uppercase()).unwrap(),
+                mode
+            );
+            assert!(serde_json::from_str::<AggregationMode>("\"mean\"").is_err());
+        }
+    }
+
+    #[test]
+    fn test_ema() {
+        let mut ema = Ema::new(0.5);
+        assert_eq!(ema.update(100.0), 100.0);
+        assert_eq!(ema.update(110.0), 105.0);
+
+        let mut ema = Ema::new(0.2);
+        ema.update(0.0);
+        for _ in 0..100 {
+            ema.update(50.0);
+        }
+        assert!((ema.value.unwrap() - 50.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ema_from_entries() {
+        let entries = [entry(2_000, 11_000), entry(1_000, 10_000)];
+        let ema = Ema::from_entries(0.5, &entries, 2);
+        assert_eq!(ema.value, Some(105.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ema alpha must be in (0, 1]")]
+    fn test_ema_rejects_zero_alpha() {
+        Ema::new(0.0);
+    }
+
+    #[test]
+    fn test_aggregate_filtered_rejects_outlier() {
+        let entries = [
+            sourced("BINANCE", 100),
+            sourced("OKX", 102),
+            sourced("BYBIT", 104),
+            sourced("BROKEN", 200),
+        ];
+        assert_eq!(AggregationMode::Median.aggregate(&entries, 0), Some(103));
+
+        assert_eq!(
+            AggregationMode::Median.aggregate_filtered(&entries, 0, 5.0),
+            Some((102, vec!["BROKEN".to_string()]))
+        );
+        assert_eq!(
+            AggregationMode::Median.aggregate_filtered(&entries, 0, 100.0),
+            Some((103, vec![]))
+        );
+        assert_eq!(
+            AggregationMode::Median.aggregate_filtered(&[], 0, 5.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(AggregationMode::Median.aggregate(&[], 1_000), None);
+        assert_eq!(AggregationMode::Twap.aggregate(&[], 1_000), None);
+    }
+
+    #[test]
+    fn test_median() {
+        let entries = [entry(3, 300), entry(1, 100), entry(2, 200)];
+        assert_eq!(AggregationMode::Median.aggregate(&entries, 0), Some(200));
+
+        let entries = [entry(1, 100), entry(2, 201), entry(3, 300), entry(4, 400)];
+        assert_eq!(AggregationMode::Median.aggregate(&entries, 0), Some(250));
+    }
+
+    #[test]
+    fn test_twap_single_sample() {
+        let entries = [entry(1_000, 42)];
+        assert_eq!(AggregationMode::Twap.aggregate(&entries, 1_000), Some(42));
+        assert_eq!(AggregationMode::Twap.aggregate(&entries, 0), Some(42));
+    }
+
+    #[test]
+    fn test_twap_out_of_order() {
+        // Once sorted, 100, 200 and 400 are each held for 1s (the last one through the tail)
+        let entries = [entry(4_000, 200), entry(5_000, 400), entry(3_000, 100)];
+        let expected = (100 * 1_000 + 200 * 1_000 + 400 * 1_000) / 3_000;
+        assert_eq!(
+            AggregationMode::Twap.aggregate(&entries, 1_000),
+            Some(expected)
+        );
+
+        let entries = [entry(3_000, 100), entry(6_000, 200), entry(7_000, 400)];
+        let expected = (100 * 3_000 + 200 * 1_000 + 400 * 1_000) / 5_000;
+        assert_eq!(
+            AggregationMode::Twap.aggregate(&entries, 1_000),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_twap_overflow() {
+        // 18 decimals prices held for long gaps overflow the weighted sum
+        let price = 1_000_000 * 10_u128.pow(30);
+        let entries = [entry(0, price), entry(1_000_000_000, price)];
+        assert_eq!(AggregationMode::Twap.aggregate(&entries, 1_000), None);
+        assert_eq!(
+            AggregationMode::Twap.aggregate(&[entry(0, price)], u64::MAX),
+            None
+        );
+
+        // Still fine with short gaps
+        let entries = [entry(0, price), entry(1, price)];
+        assert_eq!(AggregationMode::Twap.aggregate(&entries, 1), Some(price));
+    }
+
+    #[test]
+    fn test_twap_duplicate_timestamps() {
+        // The first sample at 1_000 has a zero gap and is skipped
+        let entries = [entry(1_000, 999), entry(1_000, 100), entry(2_000, 200)];
+        let expected = (100 * 1_000 + 200 * 1_000) / 2_000;
+        assert_eq!(
+            AggregationMode::Twap.aggregate(&entries, 1_000),
+            Some(expected)
+        );
+    }
 }
@@ -24,11 +24,15 @@ pub struct FundingRateEntry {
 #[cfg(feature = "proto")]
 impl FundingRateEntry {
     fn to_proto(&self) -> crate::schema::FundingRateEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::FundingRateEntry {
         crate::schema::FundingRateEntry {
-            source: self.source.clone(),
+            source: self.source,
             pair: Some(crate::schema::Pair {
-                base: self.pair.base.clone(),
-                quote: self.pair.quote.clone(),
+                base: self.pair.base,
+                quote: self.pair.quote,
             }),
             annualized_rate: self.annualized_rate,
             timestamp_ms: self.timestamp_ms,
@@ -56,7 +60,7 @@ impl FundingRateEntry {
                 quote: pair.quote,
             },
             annualized_rate: proto.annualized_rate,
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             instrument_type,
             received_timestamp_ms: proto.received_timestamp_ms,
         })
@@ -71,6 +75,13 @@ impl ProtoSerialize for FundingRateEntry {
         proto.encode_raw(&mut buf);
         buf
     }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto.encode_raw(&mut buf);
+        buf
+    }
 }
 
 #[cfg(feature = "proto")]
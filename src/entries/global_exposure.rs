@@ -1,7 +1,7 @@
 #[cfg(feature = "proto")]
 use prost::Message;
 
-use crate::contract::Contract;
+use crate::{contract::Contract, pair::AssetSymbol, web3::Chain};
 #[cfg(feature = "proto")]
 use crate::{ProtoDeserialize, ProtoSerialize};
 
@@ -19,29 +19,58 @@ pub struct GlobalExposureEntry {
     pub gross_position_size: f64,
     pub net_position_size: f64,
     pub contract: Option<Contract>,
+    /// Appended to the borsh layout, entries stored before it existed are decoded
+    /// with `legacy::GlobalExposureEntryV1`.
+    pub chain: Option<Chain>,
+}
+
+impl GlobalExposureEntry {
+    /// Returns the exposed asset as an UPPERCASE symbol, like `Pair` components
+    pub fn asset_as_symbol(&self) -> AssetSymbol {
+        self.asset.trim().to_uppercase()
+    }
 }
 
 #[cfg(feature = "proto")]
 impl GlobalExposureEntry {
     fn to_proto(&self) -> crate::schema::GlobalExposureEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::GlobalExposureEntry {
         crate::schema::GlobalExposureEntry {
-            source: self.source.clone(),
+            source: self.source,
             timestamp_ms: self.timestamp_ms,
-            asset: self.asset.clone(),
+            asset: self.asset,
             gross_position_size: self.gross_position_size,
             net_position_size: self.net_position_size,
             contract: self.contract.map(Contract::to_proto),
+            chain_option: Some(match self.chain {
+                Some(chain) => {
+                    crate::schema::global_exposure_entry::ChainOption::Chain(chain.to_proto_i32())
+                }
+                None => crate::schema::global_exposure_entry::ChainOption::NoChain(true),
+            }),
         }
     }
 
     fn from_proto(proto: crate::schema::GlobalExposureEntry) -> Result<Self, prost::DecodeError> {
+        let chain = match proto.chain_option {
+            Some(crate::schema::global_exposure_entry::ChainOption::Chain(chain)) => {
+                Some(Chain::from_proto_i32(chain)?)
+            }
+            // Missing for entries encoded before the chain was added
+            Some(crate::schema::global_exposure_entry::ChainOption::NoChain(_)) | None => None,
+        };
+
         Ok(GlobalExposureEntry {
             source: proto.source,
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             asset: proto.asset,
             gross_position_size: proto.gross_position_size,
             net_position_size: proto.net_position_size,
             contract: proto.contract.map(Contract::from_proto).transpose()?,
+            chain,
         })
     }
 }
@@ -56,6 +85,15 @@ impl ProtoSerialize for GlobalExposureEntry {
             .expect("Failed to encode GlobalExposureEntry to protobuf");
         buf
     }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto
+            .encode(&mut buf)
+            .expect("Failed to encode GlobalExposureEntry to protobuf");
+        buf
+    }
 }
 
 #[cfg(feature = "proto")]
@@ -79,10 +117,29 @@ mod tests {
             gross_position_size: 10.0,
             net_position_size: -4.0,
             contract: Some(Contract::from_raw_symbol("CLK6").unwrap()),
+            chain: None,
+        };
+
+        let decoded = GlobalExposureEntry::from_proto_bytes(&entry.to_proto_bytes()).unwrap();
+
+        assert_eq!(decoded, entry);
+    }
+
+    #[test]
+    fn proto_roundtrip_preserves_chain() {
+        let entry = GlobalExposureEntry {
+            source: "EXPOSURE_AGGREGATOR".to_string(),
+            timestamp_ms: 1,
+            asset: "eth".to_string(),
+            gross_position_size: 3.0,
+            net_position_size: 1.5,
+            contract: None,
+            chain: Some(Chain::Arbitrum),
         };
 
         let decoded = GlobalExposureEntry::from_proto_bytes(&entry.to_proto_bytes()).unwrap();
 
         assert_eq!(decoded, entry);
+        assert_eq!(decoded.asset_as_symbol(), "ETH");
     }
 }
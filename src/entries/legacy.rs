@@ -0,0 +1,89 @@
+//! Previous borsh layouts of entries.
+//!
+//! Fields are only ever appended to persisted entries. The layout an entry had
+//! before is kept here, so bytes stored with it can still be decoded and
+//! migrated, e.g `PriceEntry::from(borsh::from_slice::<PriceEntryV1>(&bytes)?)`.
+use crate::{contract::Contract, instrument_type::InstrumentType, pair::Pair, web3::Chain};
+
+use super::{GlobalExposureEntry, OpenInterestEntry, PriceEntry};
+
+/// [`PriceEntry`] layout before `decimals` was appended.
+#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+pub struct PriceEntryV1 {
+    pub source: String,
+    pub chain: Option<Chain>,
+    pub pair: Pair,
+    pub timestamp_ms: i64,
+    pub price: u128,
+    pub volume: u128,
+    pub expiration_timestamp: Option<i64>,
+    pub instrument_type: InstrumentType,
+    pub received_timestamp_ms: i64,
+}
+
+impl From<PriceEntryV1> for PriceEntry {
+    fn from(entry: PriceEntryV1) -> Self {
+        Self {
+            source: entry.source,
+            chain: entry.chain,
+            pair: entry.pair,
+            timestamp_ms: entry.timestamp_ms,
+            price: entry.price,
+            volume: entry.volume,
+            expiration_timestamp: entry.expiration_timestamp,
+            instrument_type: entry.instrument_type,
+            received_timestamp_ms: entry.received_timestamp_ms,
+            decimals: None,
+        }
+    }
+}
+
+/// [`OpenInterestEntry`] layout before `chain` was appended.
+#[derive(Debug, Clone, PartialEq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+pub struct OpenInterestEntryV1 {
+    pub source: String,
+    pub pair: Pair,
+    pub open_interest: f64,
+    pub timestamp_ms: i64,
+    pub instrument_type: InstrumentType,
+    pub received_timestamp_ms: i64,
+}
+
+impl From<OpenInterestEntryV1> for OpenInterestEntry {
+    fn from(entry: OpenInterestEntryV1) -> Self {
+        Self {
+            source: entry.source,
+            pair: entry.pair,
+            open_interest: entry.open_interest,
+            timestamp_ms: entry.timestamp_ms,
+            instrument_type: entry.instrument_type,
+            received_timestamp_ms: entry.received_timestamp_ms,
+            chain: None,
+        }
+    }
+}
+
+/// [`GlobalExposureEntry`] layout before `chain` was appended.
+#[derive(Debug, Clone, PartialEq, borsh::BorshSerialize, borsh::BorshDeserialize)]
+pub struct GlobalExposureEntryV1 {
+    pub source: String,
+    pub timestamp_ms: i64,
+    pub asset: String,
+    pub gross_position_size: f64,
+    pub net_position_size: f64,
+    pub contract: Option<Contract>,
+}
+
+impl From<GlobalExposureEntryV1> for GlobalExposureEntry {
+    fn from(entry: GlobalExposureEntryV1) -> Self {
+        Self {
+            source: entry.source,
+            timestamp_ms: entry.timestamp_ms,
+            asset: entry.asset,
+            gross_position_size: entry.gross_position_size,
+            net_position_size: entry.net_position_size,
+            contract: entry.contract,
+            chain: None,
+        }
+    }
+}
@@ -14,6 +14,11 @@ pub mod trade;
 pub mod position;
 // Global exposure entries
 pub mod global_exposure;
+// Source helpers, i.e naming & common entry accessors
+pub mod source;
+// Previous borsh layouts of entries, to decode stored data
+#[cfg(feature = "borsh")]
+pub mod legacy;
 
 pub use funding_rate::*;
 pub use global_exposure::*;
@@ -21,5 +26,17 @@ pub use open_interest::*;
 pub use orderbook::*;
 pub use position::*;
 pub use price::*;
+pub use source::*;
 pub use trade::*;
 pub use volume::*;
+
+/// Rejects non-positive timestamps when decoding entries, as they break interval bucketing.
+#[cfg(feature = "proto")]
+pub(crate) fn validate_timestamp_ms(timestamp_ms: i64) -> Result<i64, prost::DecodeError> {
+    if timestamp_ms <= 0 {
+        return Err(prost::DecodeError::new(format!(
+            "Invalid timestamp_ms value: {timestamp_ms}, must be positive",
+        )));
+    }
+    Ok(timestamp_ms)
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "proto")]
 use prost::Message;
 
-use crate::{instrument_type::InstrumentType, Pair};
+use crate::{instrument_type::InstrumentType, web3::Chain, Pair};
 #[cfg(feature = "proto")]
 use crate::{ProtoDeserialize, ProtoSerialize};
 
@@ -19,16 +19,23 @@ pub struct OpenInterestEntry {
     pub timestamp_ms: i64,
     pub instrument_type: InstrumentType,
     pub received_timestamp_ms: i64,
+    /// Appended to the borsh layout, entries stored before it existed are decoded
+    /// with `legacy::OpenInterestEntryV1`.
+    pub chain: Option<Chain>,
 }
 
 #[cfg(feature = "proto")]
 impl OpenInterestEntry {
     fn to_proto(&self) -> crate::schema::OpenInterestEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::OpenInterestEntry {
         crate::schema::OpenInterestEntry {
-            source: self.source.clone(),
+            source: self.source,
             pair: Some(crate::schema::Pair {
-                base: self.pair.base.clone(),
-                quote: self.pair.quote.clone(),
+                base: self.pair.base,
+                quote: self.pair.quote,
             }),
             open_interest: self.open_interest,
             timestamp_ms: self.timestamp_ms,
@@ -36,6 +43,12 @@ impl OpenInterestEntry {
                 InstrumentType::Spot => crate::schema::InstrumentType::Spot as i32,
                 InstrumentType::Perp => crate::schema::InstrumentType::Perp as i32,
             },
+            chain_option: Some(match self.chain {
+                Some(chain) => {
+                    crate::schema::open_interest_entry::ChainOption::Chain(chain.to_proto_i32())
+                }
+                None => crate::schema::open_interest_entry::ChainOption::NoChain(true),
+            }),
             received_timestamp_ms: self.received_timestamp_ms,
         }
     }
@@ -49,6 +62,13 @@ impl OpenInterestEntry {
             x if x == crate::schema::InstrumentType::Perp as i32 => InstrumentType::Perp,
             _ => InstrumentType::Perp, // Default to Perp for OI (backwards compat)
         };
+        let chain = match proto.chain_option {
+            Some(crate::schema::open_interest_entry::ChainOption::Chain(chain)) => {
+                Some(Chain::from_proto_i32(chain)?)
+            }
+            // Missing for entries encoded before the chain was added
+            Some(crate::schema::open_interest_entry::ChainOption::NoChain(_)) | None => None,
+        };
 
         Ok(OpenInterestEntry {
             source: proto.source,
@@ -57,8 +77,9 @@ impl OpenInterestEntry {
                 quote: pair.quote,
             },
             open_interest: proto.open_interest,
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             instrument_type,
+            chain,
             received_timestamp_ms: proto.received_timestamp_ms,
         })
     }
@@ -74,6 +95,15 @@ impl ProtoSerialize for OpenInterestEntry {
             .expect("Failed to encode OpenInterestEntry to protobuf");
         buf
     }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto
+            .encode(&mut buf)
+            .expect("Failed to encode OpenInterestEntry to protobuf");
+        buf
+    }
 }
 
 #[cfg(feature = "proto")]
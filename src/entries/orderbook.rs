@@ -1,3 +1,5 @@
+use std::collections::{hash_map::Entry, HashMap};
+
 #[cfg(feature = "proto")]
 use prost::Message;
 
@@ -20,6 +22,59 @@ pub struct OrderbookEntry {
     pub received_timestamp_ms: i64,
 }
 
+/// Inconsistency reported by [`OrderbookEntry::validate_sequence`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OrderbookSequenceError {
+    /// The entries are not for the same source, pair & instrument type.
+    #[error("Entry for {actual} follows an entry for {expected}")]
+    MarketMismatch { expected: String, actual: String },
+    /// The `update_id` did not increase.
+    #[error("Out of order update_id {update_id}, previous was {prev_update_id}")]
+    OutOfOrder { prev_update_id: u64, update_id: u64 },
+    /// A delta update skipped some `update_id`s.
+    #[error("Missing updates between update_id {prev_update_id} and {update_id}")]
+    Gap { prev_update_id: u64, update_id: u64 },
+}
+
+impl OrderbookEntry {
+    /// Checks that the entry can follow `prev` in a stream: same source, pair &
+    /// instrument type, and an increasing `update_id`.
+    ///
+    /// Delta updates must directly follow `prev`, i.e `update_id == prev + 1`.
+    pub fn validate_sequence(&self, prev: &OrderbookEntry) -> Result<(), OrderbookSequenceError> {
+        let market = |entry: &OrderbookEntry| {
+            format!("{}:{}:{}", entry.source, entry.pair, entry.instrument_type)
+        };
+        if self.source != prev.source
+            || self.pair != prev.pair
+            || self.instrument_type != prev.instrument_type
+        {
+            return Err(OrderbookSequenceError::MarketMismatch {
+                expected: market(prev),
+                actual: market(self),
+            });
+        }
+
+        let (prev_update_id, update_id) = (prev.data.update_id, self.data.update_id);
+        if update_id <= prev_update_id {
+            return Err(OrderbookSequenceError::OutOfOrder {
+                prev_update_id,
+                update_id,
+            });
+        }
+        if self.r#type == OrderbookUpdateType::Update(UpdateType::Delta)
+            && update_id != prev_update_id + 1
+        {
+            return Err(OrderbookSequenceError::Gap {
+                prev_update_id,
+                update_id,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -53,15 +108,69 @@ impl std::fmt::Display for UpdateType {
     }
 }
 
+/// One line summary, e.g `ORDERBOOK[BTC/USD SPOT] snapshot #1 2 bids 3 asks @ 145567 from TEST`.
+impl std::fmt::Display for OrderbookEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ORDERBOOK[{} {}] {} #{} {} bids {} asks @ {} from {}",
+            self.pair,
+            self.instrument_type,
+            self.r#type,
+            self.data.update_id,
+            self.data.bids.len(),
+            self.data.asks.len(),
+            self.timestamp_ms,
+            self.source
+        )
+    }
+}
+
+/// Canonical forms are `"update:target"`, `"update:delta"` and `"snapshot"`.
 impl std::fmt::Display for OrderbookUpdateType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Update(update_type) => write!(f, "update with type {update_type}"),
+            Self::Update(update_type) => write!(f, "update:{update_type}"),
             Self::Snapshot => write!(f, "snapshot"),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown orderbook update type: {0}")]
+pub struct UpdateTypeParseError(String);
+
+/// Parses the forms emitted by `Display`, i.e `"target"` or `"delta"`, ignoring case.
+impl std::str::FromStr for UpdateType {
+    type Err = UpdateTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "target" => Ok(Self::Target),
+            "delta" => Ok(Self::Delta),
+            _ => Err(UpdateTypeParseError(s.to_string())),
+        }
+    }
+}
+
+/// Parses the forms emitted by `Display`, i.e `"update:target"`, `"update:delta"`
+/// or `"snapshot"`, ignoring case.
+impl std::str::FromStr for OrderbookUpdateType {
+    type Err = UpdateTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_lowercase();
+        if normalized == "snapshot" {
+            return Ok(Self::Snapshot);
+        }
+        normalized
+            .strip_prefix("update:")
+            .and_then(|update_type| update_type.parse().ok())
+            .map(Self::Update)
+            .ok_or_else(|| UpdateTypeParseError(s.to_string()))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -75,20 +184,159 @@ pub struct OrderbookData {
     pub asks: Vec<(f64, f64)>,
 }
 
+/// Side of an orderbook level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderbookSide {
+    Bid,
+    Ask,
+}
+
+/// Inconsistency reported by [`OrderbookData::audit`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderbookIssue {
+    /// The best bid is not strictly below the best ask.
+    Crossed { best_bid: f64, best_ask: f64 },
+    /// A side holds more levels than the expected depth.
+    OverDepth {
+        side: OrderbookSide,
+        levels: usize,
+        depth: usize,
+    },
+    /// A level has a zero, negative or NaN quantity.
+    NonPositiveQuantity {
+        side: OrderbookSide,
+        price: f64,
+        quantity: f64,
+    },
+}
+
+impl OrderbookData {
+    /// Checks the self-consistency of the book: not crossed, every quantity
+    /// positive and no side holding more than `depth` levels.
+    ///
+    /// Returns every detected issue instead of stopping at the first one.
+    pub fn audit(&self, depth: usize) -> Result<(), Vec<OrderbookIssue>> {
+        let mut issues = Vec::new();
+
+        for (side, levels) in [
+            (OrderbookSide::Bid, &self.bids),
+            (OrderbookSide::Ask, &self.asks),
+        ] {
+            if levels.len() > depth {
+                issues.push(OrderbookIssue::OverDepth {
+                    side,
+                    levels: levels.len(),
+                    depth,
+                });
+            }
+
+            for &(price, quantity) in levels {
+                if quantity.is_nan() || quantity <= 0.0 {
+                    issues.push(OrderbookIssue::NonPositiveQuantity {
+                        side,
+                        price,
+                        quantity,
+                    });
+                }
+            }
+        }
+
+        let best_bid = self.bids.iter().map(|(price, _)| *price).reduce(f64::max);
+        let best_ask = self.asks.iter().map(|(price, _)| *price).reduce(f64::min);
+        if let (Some(best_bid), Some(best_ask)) = (best_bid, best_ask) {
+            if best_bid >= best_ask {
+                issues.push(OrderbookIssue::Crossed { best_bid, best_ask });
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Sorts bids by descending price and asks by ascending price.
+    ///
+    /// Levels with a NaN price are dropped.
+    pub fn sort_levels(&mut self) {
+        self.bids.retain(|(price, _)| !price.is_nan());
+        self.asks.retain(|(price, _)| !price.is_nan());
+        self.bids.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        self.asks.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    }
+
+    /// Keeps a single level per price on each side, the last one received winning.
+    ///
+    /// Levels keep the position of their first occurrence. Levels with a NaN
+    /// price are dropped.
+    pub fn dedup_levels(&mut self) {
+        let replace = |quantity: &mut f64, new: f64| *quantity = new;
+        self.bids = Self::merge_side(std::mem::take(&mut self.bids), replace);
+        self.asks = Self::merge_side(std::mem::take(&mut self.asks), replace);
+    }
+
+    /// Rounds every price to the nearest multiple of `tick`, summing the quantities
+    /// of the levels ending up at the same price.
+    ///
+    /// Levels keep the position of their first occurrence. Does nothing if `tick`
+    /// isn't positive.
+    pub fn quantize(&mut self, tick: f64) {
+        if tick.is_nan() || tick <= 0.0 {
+            return;
+        }
+        let round = |levels: Vec<(f64, f64)>| {
+            levels
+                .into_iter()
+                .map(|(price, quantity)| ((price / tick).round() * tick, quantity))
+                .collect()
+        };
+        let sum = |quantity: &mut f64, new: f64| *quantity += new;
+        self.bids = Self::merge_side(round(std::mem::take(&mut self.bids)), sum);
+        self.asks = Self::merge_side(round(std::mem::take(&mut self.asks)), sum);
+    }
+
+    /// Merges the levels sharing a price with `merge`, dropping NaN prices.
+    fn merge_side(levels: Vec<(f64, f64)>, merge: impl Fn(&mut f64, f64)) -> Vec<(f64, f64)> {
+        let mut positions: HashMap<u64, usize> = HashMap::with_capacity(levels.len());
+        let mut merged: Vec<(f64, f64)> = Vec::with_capacity(levels.len());
+        for (price, quantity) in levels {
+            if price.is_nan() {
+                continue;
+            }
+            // `+ 0.0` folds -0.0 into 0.0 so both map to the same level
+            match positions.entry((price + 0.0).to_bits()) {
+                Entry::Occupied(entry) => {
+                    merge(&mut merged[*entry.get()].1, quantity);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(merged.len());
+                    merged.push((price, quantity));
+                }
+            }
+        }
+        merged
+    }
+}
+
 #[cfg(feature = "proto")]
 impl OrderbookEntry {
     fn to_proto(&self) -> crate::schema::OrderbookEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::OrderbookEntry {
         crate::schema::OrderbookEntry {
-            source: self.source.clone(),
+            source: self.source,
             instrument_type: match self.instrument_type {
                 InstrumentType::Spot => crate::schema::InstrumentType::Spot as i32,
                 InstrumentType::Perp => crate::schema::InstrumentType::Perp as i32,
             },
             pair: Some(crate::schema::Pair {
-                base: self.pair.base.clone(),
-                quote: self.pair.quote.clone(),
+                base: self.pair.base,
+                quote: self.pair.quote,
             }),
-            r#type: Some(match &self.r#type {
+            r#type: Some(match self.r#type {
                 OrderbookUpdateType::Update(update_type) => crate::schema::OrderbookUpdateType {
                     update_type: Some(crate::schema::orderbook_update_type::UpdateType::Update(
                         match update_type {
@@ -108,20 +356,14 @@ impl OrderbookEntry {
                 bids: self
                     .data
                     .bids
-                    .iter()
-                    .map(|(price, quantity)| crate::schema::BidOrAsk {
-                        price: *price,
-                        quantity: *quantity,
-                    })
+                    .into_iter()
+                    .map(|(price, quantity)| crate::schema::BidOrAsk { price, quantity })
                     .collect(),
                 asks: self
                     .data
                     .asks
-                    .iter()
-                    .map(|(price, quantity)| crate::schema::BidOrAsk {
-                        price: *price,
-                        quantity: *quantity,
-                    })
+                    .into_iter()
+                    .map(|(price, quantity)| crate::schema::BidOrAsk { price, quantity })
                     .collect(),
             }),
             timestamp_ms: self.timestamp_ms,
@@ -206,7 +448,7 @@ impl OrderbookEntry {
             pair,
             r#type,
             data,
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             received_timestamp_ms: proto.received_timestamp_ms,
         })
     }
@@ -220,6 +462,13 @@ impl crate::ProtoSerialize for OrderbookEntry {
         proto.encode_raw(&mut buf);
         buf
     }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto.encode_raw(&mut buf);
+        buf
+    }
 }
 
 #[cfg(feature = "proto")]
@@ -228,4 +477,206 @@ impl crate::ProtoDeserialize for OrderbookEntry {
         let proto = crate::schema::OrderbookEntry::decode(bytes)?;
         Self::from_proto(proto)
     }
+
+    fn from_proto_bytes_limited(
+        bytes: &[u8],
+        limits: crate::DecodeLimits,
+    ) -> Result<Self, prost::DecodeError> {
+        limits.check_message_size(bytes)?;
+        let proto = crate::schema::OrderbookEntry::decode(bytes)?;
+        if let Some(data) = &proto.data {
+            limits.check_levels("bids", data.bids.len())?;
+            limits.check_levels("asks", data.asks.len())?;
+        }
+        Self::from_proto(proto)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::rstest]
+    #[case(OrderbookUpdateType::Update(UpdateType::Target), "update:target")]
+    #[case(OrderbookUpdateType::Update(UpdateType::Delta), "update:delta")]
+    #[case(OrderbookUpdateType::Snapshot, "snapshot")]
+    fn test_update_type_roundtrip(#[case] update_type: OrderbookUpdateType, #[case] s: &str) {
+        assert_eq!(update_type.to_string(), s);
+        assert_eq!(s.parse::<OrderbookUpdateType>().unwrap(), update_type);
+        assert_eq!(
+            s.to_uppercase().parse::<OrderbookUpdateType>().unwrap(),
+            update_type
+        );
+
+        if let OrderbookUpdateType::Update(inner) = update_type {
+            let inner_str = inner.to_string();
+            assert_eq!(inner_str.parse::<UpdateType>().unwrap(), inner);
+        }
+    }
+
+    #[rstest::rstest]
+    #[case("update")]
+    #[case("update:")]
+    #[case("update:snapshot")]
+    #[case("delta")]
+    fn test_update_type_parse_error(#[case] s: &str) {
+        assert_eq!(
+            s.parse::<OrderbookUpdateType>(),
+            Err(UpdateTypeParseError(s.to_string()))
+        );
+    }
+
+    fn delta(update_id: u64) -> OrderbookEntry {
+        OrderbookEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Spot,
+            pair: Pair::from_currencies("BTC", "USD"),
+            r#type: OrderbookUpdateType::Update(UpdateType::Delta),
+            data: OrderbookData {
+                update_id,
+                bids: vec![],
+                asks: vec![],
+            },
+            timestamp_ms: 1,
+            received_timestamp_ms: 1,
+        }
+    }
+
+    #[test]
+    fn test_validate_sequence_in_order() {
+        assert_eq!(delta(2).validate_sequence(&delta(1)), Ok(()));
+
+        let snapshot = OrderbookEntry {
+            r#type: OrderbookUpdateType::Snapshot,
+            ..delta(10)
+        };
+        assert_eq!(snapshot.validate_sequence(&delta(1)), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_sequence_gap_and_out_of_order() {
+        assert_eq!(
+            delta(4).validate_sequence(&delta(1)),
+            Err(OrderbookSequenceError::Gap {
+                prev_update_id: 1,
+                update_id: 4,
+            })
+        );
+        assert_eq!(
+            delta(1).validate_sequence(&delta(1)),
+            Err(OrderbookSequenceError::OutOfOrder {
+                prev_update_id: 1,
+                update_id: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_sequence_mismatched_pair() {
+        let other = OrderbookEntry {
+            pair: Pair::from_currencies("ETH", "USD"),
+            ..delta(2)
+        };
+        assert_eq!(
+            other.validate_sequence(&delta(1)),
+            Err(OrderbookSequenceError::MarketMismatch {
+                expected: "TEST:BTC/USD:SPOT".to_string(),
+                actual: "TEST:ETH/USD:SPOT".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_quantize() {
+        let mut data = OrderbookData {
+            update_id: 1,
+            bids: vec![(100.02, 1.0), (99.5, 3.0), (99.98, 2.0)],
+            asks: vec![(100.6, 1.0)],
+        };
+        data.quantize(0.5);
+        assert_eq!(data.bids, vec![(100.0, 3.0), (99.5, 3.0)]);
+        assert_eq!(data.asks, vec![(100.5, 1.0)]);
+
+        let before = data.clone();
+        data.quantize(0.0);
+        data.quantize(-1.0);
+        assert_eq!(data, before);
+    }
+
+    #[test]
+    fn test_sort_levels() {
+        let mut data = OrderbookData {
+            update_id: 1,
+            bids: vec![(98.0, 2.0), (f64::NAN, 1.0), (99.0, 1.0), (97.0, 3.0)],
+            asks: vec![(102.0, 2.0), (101.0, 1.0), (f64::NAN, 1.0), (103.0, 3.0)],
+        };
+
+        data.sort_levels();
+
+        assert_eq!(data.bids, vec![(99.0, 1.0), (98.0, 2.0), (97.0, 3.0)]);
+        assert_eq!(data.asks, vec![(101.0, 1.0), (102.0, 2.0), (103.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_dedup_levels() {
+        let mut data = OrderbookData {
+            update_id: 1,
+            bids: vec![(99.0, 1.0), (98.0, 2.0), (99.0, 5.0), (f64::NAN, 1.0)],
+            asks: vec![(101.0, 1.0), (101.0, 0.5), (101.0, 3.0), (102.0, 2.0)],
+        };
+
+        data.dedup_levels();
+        assert_eq!(data.bids, vec![(99.0, 5.0), (98.0, 2.0)]);
+        assert_eq!(data.asks, vec![(101.0, 3.0), (102.0, 2.0)]);
+
+        data.sort_levels();
+        assert_eq!(data.audit(2), Ok(()));
+    }
+
+    #[test]
+    fn test_audit_consistent_book() {
+        let data = OrderbookData {
+            update_id: 1,
+            bids: vec![(99.0, 1.0), (98.0, 2.0)],
+            asks: vec![(101.0, 1.0), (102.0, 2.0)],
+        };
+
+        assert_eq!(data.audit(2), Ok(()));
+    }
+
+    #[test]
+    fn test_audit_reports_every_issue() {
+        let data = OrderbookData {
+            update_id: 1,
+            bids: vec![(102.0, 1.0), (99.0, 0.0), (98.0, 2.0)],
+            asks: vec![(101.0, -1.0)],
+        };
+
+        let issues = data.audit(2).unwrap_err();
+
+        assert_eq!(
+            issues,
+            vec![
+                OrderbookIssue::OverDepth {
+                    side: OrderbookSide::Bid,
+                    levels: 3,
+                    depth: 2,
+                },
+                OrderbookIssue::NonPositiveQuantity {
+                    side: OrderbookSide::Bid,
+                    price: 99.0,
+                    quantity: 0.0,
+                },
+                OrderbookIssue::NonPositiveQuantity {
+                    side: OrderbookSide::Ask,
+                    price: 101.0,
+                    quantity: -1.0,
+                },
+                OrderbookIssue::Crossed {
+                    best_bid: 102.0,
+                    best_ask: 101.0,
+                },
+            ]
+        );
+    }
 }
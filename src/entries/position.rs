@@ -24,15 +24,19 @@ pub struct PositionEntry {
 #[cfg(feature = "proto")]
 impl PositionEntry {
     fn to_proto(&self) -> crate::schema::PositionEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::PositionEntry {
         crate::schema::PositionEntry {
-            source: self.source.clone(),
+            source: self.source,
             instrument_type: match self.instrument_type {
                 InstrumentType::Spot => crate::schema::InstrumentType::Spot as i32,
                 InstrumentType::Perp => crate::schema::InstrumentType::Perp as i32,
             },
             pair: Some(crate::schema::Pair {
-                base: self.pair.base.clone(),
-                quote: self.pair.quote.clone(),
+                base: self.pair.base,
+                quote: self.pair.quote,
             }),
             timestamp_ms: self.timestamp_ms,
             received_timestamp_ms: self.received_timestamp_ms,
@@ -78,7 +82,7 @@ impl PositionEntry {
                 base: pair.base,
                 quote: pair.quote,
             },
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             received_timestamp_ms: proto.received_timestamp_ms,
             side,
             size: proto.size,
@@ -96,6 +100,15 @@ impl ProtoSerialize for PositionEntry {
             .expect("Failed to encode PositionEntry to protobuf");
         buf
     }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto
+            .encode(&mut buf)
+            .expect("Failed to encode PositionEntry to protobuf");
+        buf
+    }
 }
 #[cfg(feature = "proto")]
 impl ProtoDeserialize for PositionEntry {
@@ -3,7 +3,7 @@ use prost::Message;
 
 use crate::{instrument_type::InstrumentType, pair::Pair, web3::Chain};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
     feature = "borsh",
@@ -20,37 +20,67 @@ pub struct PriceEntry {
     pub expiration_timestamp: Option<i64>,
     pub instrument_type: InstrumentType,
     pub received_timestamp_ms: i64,
+    /// Number of decimals of `price` & `volume`, when known.
+    ///
+    /// Appended to the borsh layout, entries stored before it existed are decoded
+    /// with `legacy::PriceEntryV1`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub decimals: Option<u32>,
+}
+
+/// One line summary, e.g `PRICE[BTC/USD SPOT] 12000 vol 0 @ 145567 from TEST`.
+impl std::fmt::Display for PriceEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PRICE[{} {}] {} vol {} @ {} from {}",
+            self.pair,
+            self.instrument_type,
+            self.price,
+            self.volume,
+            self.timestamp_ms,
+            self.source
+        )
+    }
+}
+
+impl PriceEntry {
+    /// Returns the age of the entry, in milliseconds
+    pub fn age_ms(&self, now_ms: i64) -> i64 {
+        now_ms - self.timestamp_ms
+    }
+
+    /// Returns true if the entry is older than `max_age_ms`
+    pub fn is_stale(&self, now_ms: i64, max_age_ms: i64) -> bool {
+        self.age_ms(now_ms) > max_age_ms
+    }
+
+    /// Returns true if the instrument has an expiration (in ms) that is reached
+    pub fn is_expired(&self, now_ms: i64) -> bool {
+        self.expiration_timestamp
+            .is_some_and(|expiration_timestamp| now_ms >= expiration_timestamp)
+    }
 }
 
 #[cfg(feature = "proto")]
 impl PriceEntry {
     fn to_proto(&self) -> crate::schema::PriceEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::PriceEntry {
         crate::schema::PriceEntry {
-            source: self.source.clone(),
-            chain_option: match &self.chain {
-                Some(chain) => Some(crate::schema::price_entry::ChainOption::Chain(
-                    match chain {
-                        Chain::Starknet => crate::schema::Chain::Starknet as i32,
-                        Chain::Solana => crate::schema::Chain::Solana as i32,
-                        Chain::Sui => crate::schema::Chain::Sui as i32,
-                        Chain::Aptos => crate::schema::Chain::Aptos as i32,
-                        Chain::Ethereum => crate::schema::Chain::Ethereum as i32,
-                        Chain::Base => crate::schema::Chain::Base as i32,
-                        Chain::Arbitrum => crate::schema::Chain::Arbitrum as i32,
-                        Chain::Optimism => crate::schema::Chain::Optimism as i32,
-                        Chain::ZkSync => crate::schema::Chain::Zksync as i32,
-                        Chain::Polygon => crate::schema::Chain::Polygon as i32,
-                        Chain::Bnb => crate::schema::Chain::Bnb as i32,
-                        Chain::Avalanche => crate::schema::Chain::Avalanche as i32,
-                        Chain::Gnosis => crate::schema::Chain::Gnosis as i32,
-                        Chain::Worldchain => crate::schema::Chain::Worldchain as i32,
-                    },
-                )),
-                None => Some(crate::schema::price_entry::ChainOption::NoChain(true)),
-            },
+            source: self.source,
+            chain_option: Some(match self.chain {
+                Some(chain) => crate::schema::price_entry::ChainOption::Chain(chain.to_proto_i32()),
+                None => crate::schema::price_entry::ChainOption::NoChain(true),
+            }),
             pair: Some(crate::schema::Pair {
-                base: self.pair.base.clone(),
-                quote: self.pair.quote.clone(),
+                base: self.pair.base,
+                quote: self.pair.quote,
             }),
             timestamp_ms: self.timestamp_ms,
             price: Some(crate::schema::UInt128 {
@@ -70,33 +100,19 @@ impl PriceEntry {
                 InstrumentType::Perp => crate::schema::InstrumentType::Perp as i32,
             },
             received_timestamp_ms: self.received_timestamp_ms,
+            decimals_option: Some(match self.decimals {
+                Some(decimals) => crate::schema::price_entry::DecimalsOption::Decimals(decimals),
+                None => crate::schema::price_entry::DecimalsOption::NoDecimals(true),
+            }),
         }
     }
 
     fn from_proto(proto: crate::schema::PriceEntry) -> Result<Self, prost::DecodeError> {
         let chain = match proto.chain_option {
             Some(crate::schema::price_entry::ChainOption::NoChain(_)) => None,
-            Some(crate::schema::price_entry::ChainOption::Chain(chain)) => Some(match chain {
-                x if x == crate::schema::Chain::Starknet as i32 => Chain::Starknet,
-                x if x == crate::schema::Chain::Solana as i32 => Chain::Solana,
-                x if x == crate::schema::Chain::Sui as i32 => Chain::Sui,
-                x if x == crate::schema::Chain::Aptos as i32 => Chain::Aptos,
-                x if x == crate::schema::Chain::Ethereum as i32 => Chain::Ethereum,
-                x if x == crate::schema::Chain::Base as i32 => Chain::Base,
-                x if x == crate::schema::Chain::Arbitrum as i32 => Chain::Arbitrum,
-                x if x == crate::schema::Chain::Optimism as i32 => Chain::Optimism,
-                x if x == crate::schema::Chain::Zksync as i32 => Chain::ZkSync,
-                x if x == crate::schema::Chain::Polygon as i32 => Chain::Polygon,
-                x if x == crate::schema::Chain::Bnb as i32 => Chain::Bnb,
-                x if x == crate::schema::Chain::Avalanche as i32 => Chain::Avalanche,
-                x if x == crate::schema::Chain::Gnosis as i32 => Chain::Gnosis,
-                x if x == crate::schema::Chain::Worldchain as i32 => Chain::Worldchain,
-                _ => {
-                    return Err(prost::DecodeError::new(format!(
-                        "Unknown chain value: {chain}",
-                    )))
-                }
-            }),
+            Some(crate::schema::price_entry::ChainOption::Chain(chain)) => {
+                Some(Chain::from_proto_i32(chain)?)
+            }
             None => {
                 return Err(prost::DecodeError::new(
                     "Missing chain_option field in PriceEntry".to_string(),
@@ -138,16 +154,23 @@ impl PriceEntry {
             _ => InstrumentType::Spot, // Default for backwards compatibility
         };
 
+        // Entries encoded before `decimals` existed have no decimals option
+        let decimals = match proto.decimals_option {
+            Some(crate::schema::price_entry::DecimalsOption::Decimals(decimals)) => Some(decimals),
+            Some(crate::schema::price_entry::DecimalsOption::NoDecimals(_)) | None => None,
+        };
+
         Ok(PriceEntry {
             source: proto.source,
             chain,
             pair,
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             price,
             volume,
             expiration_timestamp,
             instrument_type,
             received_timestamp_ms: proto.received_timestamp_ms,
+            decimals,
         })
     }
 }
@@ -160,6 +183,13 @@ impl crate::ProtoSerialize for PriceEntry {
         proto.encode_raw(&mut buf);
         buf
     }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto.encode_raw(&mut buf);
+        buf
+    }
 }
 
 #[cfg(feature = "proto")]
@@ -169,3 +199,282 @@ impl crate::ProtoDeserialize for PriceEntry {
         Self::from_proto(proto)
     }
 }
+
+/// Serde helpers (de)serializing a [`PriceEntry`] with `price` & `volume` as
+/// decimal strings, e.g `12000` with 2 decimals is written `"120.00"`.
+///
+/// Amounts are always strings so no precision is lost, entries without
+/// `decimals` are written as plain integers, e.g `"12000"`.
+/// Use with `#[serde(with = "price_as_decimal")]` on a `PriceEntry` field.
+#[cfg(feature = "serde")]
+pub mod price_as_decimal {
+    use serde::{
+        de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::PriceEntry;
+    use crate::{instrument_type::InstrumentType, pair::Pair, web3::Chain};
+
+    /// `PriceEntry` with decimal string amounts. Entries are destructured without
+    /// `..` both ways, so a field added to `PriceEntry` fails to compile here
+    /// instead of being silently dropped.
+    #[derive(Serialize, Deserialize)]
+    struct DecimalPriceEntry {
+        source: String,
+        chain: Option<Chain>,
+        pair: Pair,
+        timestamp_ms: i64,
+        price: String,
+        volume: String,
+        expiration_timestamp: Option<i64>,
+        instrument_type: InstrumentType,
+        received_timestamp_ms: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        decimals: Option<u32>,
+    }
+
+    /// Most decimals an amount can have, as `10^39` doesn't fit a `u128`.
+    const MAX_DECIMALS: u32 = 38;
+
+    pub fn serialize<S: Serializer>(entry: &PriceEntry, serializer: S) -> Result<S::Ok, S::Error> {
+        let PriceEntry {
+            source,
+            chain,
+            pair,
+            timestamp_ms,
+            price,
+            volume,
+            expiration_timestamp,
+            instrument_type,
+            received_timestamp_ms,
+            decimals,
+        } = entry;
+        let amount_decimals = checked_decimals(*decimals).map_err(S::Error::custom)?;
+        DecimalPriceEntry {
+            source: source.clone(),
+            chain: *chain,
+            pair: pair.clone(),
+            timestamp_ms: *timestamp_ms,
+            price: format_amount(*price, amount_decimals),
+            volume: format_amount(*volume, amount_decimals),
+            expiration_timestamp: *expiration_timestamp,
+            instrument_type: *instrument_type,
+            received_timestamp_ms: *received_timestamp_ms,
+            decimals: *decimals,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PriceEntry, D::Error> {
+        let DecimalPriceEntry {
+            source,
+            chain,
+            pair,
+            timestamp_ms,
+            price,
+            volume,
+            expiration_timestamp,
+            instrument_type,
+            received_timestamp_ms,
+            decimals,
+        } = DecimalPriceEntry::deserialize(deserializer)?;
+        let amount_decimals = checked_decimals(decimals).map_err(D::Error::custom)?;
+        Ok(PriceEntry {
+            source,
+            chain,
+            pair,
+            timestamp_ms,
+            price: parse_amount(&price, amount_decimals).map_err(D::Error::custom)?,
+            volume: parse_amount(&volume, amount_decimals).map_err(D::Error::custom)?,
+            expiration_timestamp,
+            instrument_type,
+            received_timestamp_ms,
+            decimals,
+        })
+    }
+
+    /// Rejects decimals that can't fit a `u128`, before they are used as a format width.
+    fn checked_decimals(decimals: Option<u32>) -> Result<u32, String> {
+        match decimals.unwrap_or(0) {
+            decimals if decimals > MAX_DECIMALS => Err(format!(
+                "Invalid decimals {decimals}, must be at most {MAX_DECIMALS}"
+            )),
+            decimals => Ok(decimals),
+        }
+    }
+
+    fn format_amount(amount: u128, decimals: u32) -> String {
+        let decimals = decimals as usize;
+        if decimals == 0 {
+            return amount.to_string();
+        }
+        let digits = format!("{amount:0>width$}", width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        format!("{integer}.{fraction}")
+    }
+
+    fn parse_amount(amount: &str, decimals: u32) -> Result<u128, String> {
+        let decimals = decimals as usize;
+        let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        if fraction.len() > decimals {
+            return Err(format!("Amount {amount} has more than {decimals} decimals"));
+        }
+        format!("{integer}{fraction:0<decimals$}")
+            .parse()
+            .map_err(|e| format!("Invalid amount {amount}: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn entry(timestamp_ms: i64, expiration_timestamp: Option<i64>) -> PriceEntry {
+        PriceEntry {
+            source: "TEST".to_string(),
+            chain: None,
+            pair: Pair::from_currencies("BTC", "USD"),
+            timestamp_ms,
+            price: 12000,
+            volume: 0,
+            expiration_timestamp,
+            instrument_type: InstrumentType::Perp,
+            received_timestamp_ms: timestamp_ms,
+            decimals: None,
+        }
+    }
+
+    #[test]
+    fn test_staleness() {
+        let fresh = entry(10_000, None);
+        assert_eq!(fresh.age_ms(12_000), 2_000);
+        assert!(!fresh.is_stale(12_000, 5_000));
+        assert!(!fresh.is_expired(12_000));
+
+        let stale = entry(1_000, None);
+        assert!(stale.is_stale(12_000, 5_000));
+        assert!(!stale.is_expired(12_000));
+    }
+
+    #[test]
+    fn test_expired() {
+        let expiring = entry(10_000, Some(11_000));
+        assert!(!expiring.is_expired(10_500));
+        assert!(expiring.is_expired(11_000));
+        assert!(expiring.is_expired(12_000));
+        assert!(!expiring.is_stale(12_000, 5_000));
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_roundtrip() {
+        for (chain, decimals) in [(None, None), (Some(Chain::Solana), Some(2))] {
+            let entry = PriceEntry {
+                chain,
+                decimals,
+                ..entry(10_000, Some(11_000))
+            };
+            let bytes = borsh::to_vec(&entry).unwrap();
+            assert_eq!(borsh::from_slice::<PriceEntry>(&bytes).unwrap(), entry);
+        }
+    }
+
+    #[test]
+    fn test_dedup_price_entries() {
+        let entry = PriceEntry {
+            source: "TEST".to_string(),
+            chain: None,
+            pair: Pair::from_currencies("BTC", "USD"),
+            timestamp_ms: 145567,
+            price: 12000,
+            volume: 0,
+            expiration_timestamp: None,
+            instrument_type: InstrumentType::Spot,
+            received_timestamp_ms: 145577,
+            decimals: None,
+        };
+        let other = PriceEntry {
+            price: 12001,
+            ..entry.clone()
+        };
+
+        let entries: HashSet<PriceEntry> = [entry.clone(), other.clone(), entry.clone()]
+            .into_iter()
+            .collect();
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries.contains(&entry));
+        assert!(entries.contains(&other));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_price_as_decimal_roundtrip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Wrapper(#[serde(with = "price_as_decimal")] PriceEntry);
+
+        let price = Wrapper(PriceEntry {
+            volume: 5,
+            decimals: Some(2),
+            ..entry(10_000, None)
+        });
+
+        let json = serde_json::to_string(&price).unwrap();
+        assert!(json.contains("\"price\":\"120.00\""), "{json}");
+        assert!(json.contains("\"volume\":\"0.05\""), "{json}");
+        assert_eq!(serde_json::from_str::<Wrapper>(&json).unwrap(), price);
+
+        let raw = Wrapper(entry(10_000, None));
+        let json = serde_json::to_string(&raw).unwrap();
+        assert!(json.contains("\"price\":\"12000\""), "{json}");
+        assert_eq!(serde_json::from_str::<Wrapper>(&json).unwrap(), raw);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_price_as_decimal_rejects_extra_decimals() {
+        let json = serde_json::to_string(&PriceEntry {
+            decimals: Some(2),
+            ..entry(10_000, None)
+        })
+        .unwrap()
+        .replace("12000", "\"120.001\"");
+
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        assert!(price_as_decimal::deserialize(&mut deserializer).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_price_as_decimal_rejects_too_many_decimals() {
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Wrapper(#[serde(with = "price_as_decimal")] PriceEntry);
+
+        let json = serde_json::to_string(&Wrapper(PriceEntry {
+            decimals: Some(38),
+            ..entry(10_000, None)
+        }))
+        .unwrap();
+        assert!(
+            json.contains("\"price\":\"0.00000000000000000000000000000000012000\""),
+            "{json}"
+        );
+        assert!(serde_json::from_str::<Wrapper>(&json).is_ok());
+
+        let json = json.replace("\"decimals\":38", "\"decimals\":4000000000");
+        let err = serde_json::from_str::<Wrapper>(&json).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid decimals 4000000000"),
+            "{err}"
+        );
+
+        let err = serde_json::to_string(&Wrapper(PriceEntry {
+            decimals: Some(39),
+            ..entry(10_000, None)
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid decimals 39"), "{err}");
+    }
+}
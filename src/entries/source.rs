@@ -0,0 +1,328 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use crate::{instrument_type::InstrumentType, pair::Pair, web3::Chain};
+
+use super::{
+    FundingRateEntry, GlobalExposureEntry, OpenInterestEntry, OrderbookEntry, PositionEntry,
+    PriceEntry, TradeEntry, VolumeEntry,
+};
+
+/// Common accessors shared by every entry, so pipelines can handle them generically.
+pub trait Sourced {
+    /// Name of the source that produced the entry
+    fn source(&self) -> &str;
+
+    /// Timestamp of the entry at the source, in milliseconds
+    fn timestamp_ms(&self) -> i64;
+
+    /// Normalized name of the source, so casing differences compare equal
+    fn source_normalized(&self) -> Source {
+        Source::new(self.source())
+    }
+}
+
+/// Name of a data source, trimmed & uppercased on construction.
+///
+/// Entries keep storing a plain `String` source, use [`Sourced::source_normalized`]
+/// to compare them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Source(String);
+
+impl Source {
+    pub fn new(source: &str) -> Self {
+        Self(source.trim().to_uppercase())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Source {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Source {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Source {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::new(&s))
+    }
+}
+
+macro_rules! impl_sourced {
+    ($($entry:ty),+ $(,)?) => {
+        $(
+            impl Sourced for $entry {
+                fn source(&self) -> &str {
+                    &self.source
+                }
+
+                fn timestamp_ms(&self) -> i64 {
+                    self.timestamp_ms
+                }
+            }
+        )+
+    };
+}
+
+impl_sourced!(
+    PriceEntry,
+    OrderbookEntry,
+    FundingRateEntry,
+    OpenInterestEntry,
+    VolumeEntry,
+    TradeEntry,
+    PositionEntry,
+    GlobalExposureEntry,
+);
+
+/// Identifies the market an entry belongs to, e.g to keep the latest entry per
+/// source & pair in a map. The source is normalized, so casing doesn't matter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntryKey {
+    pub source: Source,
+    pub pair: Pair,
+    pub instrument_type: InstrumentType,
+}
+
+macro_rules! impl_entry_key {
+    ($($entry:ty),+ $(,)?) => {
+        $(
+            impl $entry {
+                /// Returns the [`EntryKey`] of the entry
+                pub fn key(&self) -> EntryKey {
+                    EntryKey {
+                        source: Source::new(&self.source),
+                        pair: self.pair.clone(),
+                        instrument_type: self.instrument_type,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_entry_key!(PriceEntry, OrderbookEntry, TradeEntry);
+
+/// Returns the canonical source name of an on-chain feed, i.e `venue@chain`.
+///
+/// Both parts are lowercased, e.g `("JediSwap", Chain::Starknet)` -> `"jediswap@starknet"`.
+pub fn onchain_source_name(venue: &str, chain: Chain) -> String {
+    format!(
+        "{}@{}",
+        venue.trim().to_lowercase(),
+        chain.to_string().to_lowercase()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("jediswap", Chain::Starknet, "jediswap@starknet")]
+    #[case("JediSwap", Chain::Starknet, "jediswap@starknet")]
+    #[case(" UNISWAP ", Chain::Ethereum, "uniswap@ethereum")]
+    #[case("Raydium", Chain::Solana, "raydium@solana")]
+    #[case("SyncSwap", Chain::ZkSync, "syncswap@zksync")]
+    fn test_onchain_source_name(#[case] venue: &str, #[case] chain: Chain, #[case] expected: &str) {
+        assert_eq!(onchain_source_name(venue, chain), expected);
+    }
+
+    #[rstest]
+    #[case("BINANCE")]
+    #[case("binance")]
+    #[case(" Binance ")]
+    fn test_source_normalization(#[case] raw: &str) {
+        let source: Source = raw.parse().unwrap();
+        assert_eq!(source, Source::new("BINANCE"));
+        assert_eq!(source.to_string(), "BINANCE");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_source_serde() {
+        let source: Source = serde_json::from_str("\"binance\"").unwrap();
+        assert_eq!(source.as_str(), "BINANCE");
+        assert_eq!(serde_json::to_string(&source).unwrap(), "\"BINANCE\"");
+    }
+
+    #[test]
+    fn test_entry_key() {
+        use std::collections::HashMap;
+
+        use crate::entries::TradeSide;
+
+        let trade = |trade_id: &str, timestamp_ms: i64| TradeEntry {
+            source: "BINANCE".to_string(),
+            instrument_type: InstrumentType::Perp,
+            pair: Pair::from_currencies("BTC", "USD"),
+            trade_id: trade_id.to_string(),
+            buyer_address: String::new(),
+            seller_address: String::new(),
+            side: TradeSide::Buy,
+            size: 1.0,
+            price: 100.0,
+            timestamp_ms,
+            received_timestamp_ms: timestamp_ms,
+        };
+
+        let (first, second) = (trade("1", 1), trade("2", 2));
+        assert_eq!(first.key(), second.key());
+
+        let mut latest = HashMap::new();
+        for entry in [first, second] {
+            latest.insert(entry.key(), entry);
+        }
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[&trade("3", 3).key()].trade_id, "2");
+
+        let spot = TradeEntry {
+            instrument_type: InstrumentType::Spot,
+            ..trade("4", 4)
+        };
+        assert_ne!(spot.key(), trade("4", 4).key());
+
+        let lowercase = TradeEntry {
+            source: " binance".to_string(),
+            ..trade("5", 5)
+        };
+        assert_eq!(lowercase.key(), trade("5", 5).key());
+        assert_eq!(lowercase.key().source, Source::new("BINANCE"));
+    }
+
+    #[test]
+    fn test_entries_display() {
+        let price = PriceEntry {
+            source: "TEST".to_string(),
+            chain: None,
+            pair: Pair::from_currencies("BTC", "USD"),
+            timestamp_ms: 145567,
+            price: 12000,
+            volume: 0,
+            expiration_timestamp: None,
+            instrument_type: InstrumentType::Spot,
+            received_timestamp_ms: 145577,
+            decimals: None,
+        };
+        assert_eq!(
+            price.to_string(),
+            "PRICE[BTC/USD SPOT] 12000 vol 0 @ 145567 from TEST"
+        );
+
+        let trade = TradeEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Perp,
+            pair: Pair::from_currencies("ETH", "USD"),
+            trade_id: "1".to_string(),
+            buyer_address: String::new(),
+            seller_address: String::new(),
+            side: crate::entries::TradeSide::Sell,
+            size: 0.5,
+            price: 2000.0,
+            timestamp_ms: 145567,
+            received_timestamp_ms: 145577,
+        };
+        assert_eq!(
+            trade.to_string(),
+            "TRADE[ETH/USD PERP] SELL 0.5 at 2000 @ 145567 from TEST"
+        );
+
+        let volume = VolumeEntry {
+            source: "KRAKEN".to_string(),
+            instrument_type: InstrumentType::Spot,
+            pair: Pair::from_currencies("BTC", "USD"),
+            volume_daily: 1_000.0,
+            timestamp_ms: 1,
+            received_timestamp_ms: 2,
+        };
+        assert_eq!(
+            volume.to_string(),
+            "VOLUME[BTC/USD SPOT] 1000 daily @ 1 from KRAKEN"
+        );
+
+        let orderbook = OrderbookEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Spot,
+            pair: Pair::from_currencies("BTC", "USD"),
+            r#type: crate::entries::OrderbookUpdateType::Snapshot,
+            data: crate::entries::OrderbookData {
+                update_id: 1,
+                bids: vec![(99.0, 1.0), (98.0, 1.0)],
+                asks: vec![(101.0, 1.0)],
+            },
+            timestamp_ms: 145567,
+            received_timestamp_ms: 145577,
+        };
+        assert_eq!(
+            orderbook.to_string(),
+            "ORDERBOOK[BTC/USD SPOT] snapshot #1 2 bids 1 asks @ 145567 from TEST"
+        );
+    }
+
+    #[test]
+    fn test_sourced_heterogeneous_entries() {
+        let entries: Vec<Box<dyn Sourced>> = vec![
+            Box::new(VolumeEntry {
+                source: "KRAKEN".to_string(),
+                instrument_type: InstrumentType::Spot,
+                pair: Pair::from_currencies("BTC", "USD"),
+                volume_daily: 1_000.0,
+                timestamp_ms: 1,
+                received_timestamp_ms: 2,
+            }),
+            Box::new(GlobalExposureEntry {
+                source: "EXPOSURE_AGGREGATOR".to_string(),
+                timestamp_ms: 3,
+                asset: "ETH".to_string(),
+                gross_position_size: 1.0,
+                net_position_size: 1.0,
+                contract: None,
+                chain: None,
+            }),
+        ];
+
+        let fields: Vec<(&str, i64)> = entries
+            .iter()
+            .map(|entry| (entry.source(), entry.timestamp_ms()))
+            .collect();
+        assert_eq!(fields, vec![("KRAKEN", 1), ("EXPOSURE_AGGREGATOR", 3)]);
+
+        let price = |source: &str| PriceEntry {
+            source: source.to_string(),
+            chain: None,
+            instrument_type: InstrumentType::Spot,
+            pair: Pair::from_currencies("BTC", "USD"),
+            timestamp_ms: 1,
+            received_timestamp_ms: 2,
+            decimals: None,
+            price: 100,
+            volume: 0,
+            expiration_timestamp: None,
+        };
+        assert_eq!(
+            price("Binance").source_normalized(),
+            price("BINANCE").source_normalized()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(feature = "proto")]
 use prost::Message;
 
+use super::position::PositionEntry;
 use crate::{instrument_type::InstrumentType, pair::Pair, trading::Side};
 #[cfg(feature = "proto")]
 use crate::{ProtoDeserialize, ProtoSerialize};
@@ -26,6 +27,25 @@ pub struct TradeEntry {
     pub received_timestamp_ms: i64,
 }
 
+impl TradeEntry {
+    /// Converts the executed trade into the position delta it produced.
+    ///
+    /// `PositionEntry` has no USD notional, so none is required here; the
+    /// delta is expressed in base asset `size`, with no contract attached.
+    pub fn to_position_delta(&self, received_timestamp_ms: i64) -> PositionEntry {
+        PositionEntry {
+            source: self.source.clone(),
+            instrument_type: self.instrument_type,
+            pair: self.pair.clone(),
+            timestamp_ms: self.timestamp_ms,
+            received_timestamp_ms,
+            side: self.side.clone(),
+            size: self.size,
+            contract: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -38,6 +58,21 @@ pub enum TradeSide {
     Sell,
 }
 
+/// One line summary, e.g `TRADE[BTC/USD PERP] BUY 0.5 at 100000 @ 145567 from TEST`.
+impl std::fmt::Display for TradeEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let side = match self.side {
+            TradeSide::Buy => "BUY",
+            TradeSide::Sell => "SELL",
+        };
+        write!(
+            f,
+            "TRADE[{} {}] {side} {} at {} @ {} from {}",
+            self.pair, self.instrument_type, self.size, self.price, self.timestamp_ms, self.source
+        )
+    }
+}
+
 impl From<TradeSide> for Side {
     fn from(value: TradeSide) -> Self {
         match value {
@@ -50,19 +85,23 @@ impl From<TradeSide> for Side {
 #[cfg(feature = "proto")]
 impl TradeEntry {
     fn to_proto(&self) -> crate::schema::TradeEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::TradeEntry {
         crate::schema::TradeEntry {
-            source: self.source.clone(),
+            source: self.source,
             instrument_type: match self.instrument_type {
                 InstrumentType::Spot => crate::schema::InstrumentType::Spot as i32,
                 InstrumentType::Perp => crate::schema::InstrumentType::Perp as i32,
             },
             pair: Some(crate::schema::Pair {
-                base: self.pair.base.clone(),
-                quote: self.pair.quote.clone(),
+                base: self.pair.base,
+                quote: self.pair.quote,
             }),
-            buyer_address: self.buyer_address.clone(),
-            seller_address: self.seller_address.clone(),
-            trade_id: self.trade_id.clone(),
+            buyer_address: self.buyer_address,
+            seller_address: self.seller_address,
+            trade_id: self.trade_id,
             side: match self.side {
                 TradeSide::Buy => crate::schema::TradeSide::Buy as i32,
                 TradeSide::Sell => crate::schema::TradeSide::Sell as i32,
@@ -114,7 +153,7 @@ impl TradeEntry {
             side,
             size: proto.size,
             price: proto.price,
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             received_timestamp_ms: proto.received_timestamp_ms,
         })
     }
@@ -127,7 +166,16 @@ impl ProtoSerialize for TradeEntry {
         let mut buf = Vec::new();
         proto
             .encode(&mut buf)
-            .expect("Failed to encode VolumeEntry to protobuf");
+            .expect("Failed to encode TradeEntry to protobuf");
+        buf
+    }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto
+            .encode(&mut buf)
+            .expect("Failed to encode TradeEntry to protobuf");
         buf
     }
 }
@@ -139,3 +187,41 @@ impl ProtoDeserialize for TradeEntry {
         Self::from_proto(proto)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_position_delta() {
+        let trade = TradeEntry {
+            source: "HYPERLIQUID".to_string(),
+            instrument_type: InstrumentType::Perp,
+            pair: Pair::from_currencies("BTC", "USD"),
+            trade_id: "0x4567576".into(),
+            buyer_address: "0x1234567890".into(),
+            seller_address: "0xabcdef1234567890".into(),
+            side: TradeSide::Buy,
+            size: 0.25,
+            price: 101_024.0,
+            timestamp_ms: 145_567,
+            received_timestamp_ms: 145_577,
+        };
+
+        let position = trade.to_position_delta(145_600);
+
+        assert_eq!(
+            position,
+            PositionEntry {
+                source: "HYPERLIQUID".to_string(),
+                instrument_type: InstrumentType::Perp,
+                pair: Pair::from_currencies("BTC", "USD"),
+                timestamp_ms: 145_567,
+                received_timestamp_ms: 145_600,
+                side: TradeSide::Buy,
+                size: 0.25,
+                contract: None,
+            }
+        );
+    }
+}
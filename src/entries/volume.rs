@@ -21,18 +21,33 @@ pub struct VolumeEntry {
     pub received_timestamp_ms: i64,
 }
 
+/// One line summary, e.g `VOLUME[BTC/USD SPOT] 1000 daily @ 145567 from TEST`.
+impl std::fmt::Display for VolumeEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "VOLUME[{} {}] {} daily @ {} from {}",
+            self.pair, self.instrument_type, self.volume_daily, self.timestamp_ms, self.source
+        )
+    }
+}
+
 #[cfg(feature = "proto")]
 impl VolumeEntry {
     fn to_proto(&self) -> crate::schema::VolumeEntry {
+        self.clone().into_proto()
+    }
+
+    fn into_proto(self) -> crate::schema::VolumeEntry {
         crate::schema::VolumeEntry {
-            source: self.source.clone(),
+            source: self.source,
             instrument_type: match self.instrument_type {
                 InstrumentType::Spot => crate::schema::InstrumentType::Spot as i32,
                 InstrumentType::Perp => crate::schema::InstrumentType::Perp as i32,
             },
             pair: Some(crate::schema::Pair {
-                base: self.pair.base.clone(),
-                quote: self.pair.quote.clone(),
+                base: self.pair.base,
+                quote: self.pair.quote,
             }),
             volume_daily: self.volume_daily,
             timestamp_ms: self.timestamp_ms,
@@ -64,7 +79,7 @@ impl VolumeEntry {
                 quote: pair.quote,
             },
             volume_daily: proto.volume_daily,
-            timestamp_ms: proto.timestamp_ms,
+            timestamp_ms: super::validate_timestamp_ms(proto.timestamp_ms)?,
             received_timestamp_ms: proto.received_timestamp_ms,
         })
     }
@@ -80,6 +95,15 @@ impl ProtoSerialize for VolumeEntry {
             .expect("Failed to encode VolumeEntry to protobuf");
         buf
     }
+
+    fn into_proto_bytes(self) -> Vec<u8> {
+        let proto = self.into_proto();
+        let mut buf = Vec::new();
+        proto
+            .encode(&mut buf)
+            .expect("Failed to encode VolumeEntry to protobuf");
+        buf
+    }
 }
 
 #[cfg(feature = "proto")]
@@ -1,6 +1,6 @@
 use crate::{
     pair::{AssetSymbol, RawMarketName},
-    Pair,
+    InstrumentType, Pair,
 };
 
 pub mod margin_type;
@@ -35,6 +35,10 @@ pub enum Exchange {
     Extended,
 }
 
+/// Hyperliquid assets quoted per 1000 units, e.g `kPEPE` is 1000 PEPE.
+const HYPERLIQUID_KILO_ASSETS: [&str; 7] =
+    ["PEPE", "SHIB", "BONK", "FLOKI", "LUNC", "DOGS", "NEIRO"];
+
 impl Exchange {
     // TODO: add instrument type argument ?
     /// Returns the market name for the market `pair`
@@ -66,17 +70,14 @@ impl Exchange {
 
     pub fn asset_symbol_from_raw_market_name(&self, market_name: &RawMarketName) -> AssetSymbol {
         match self {
-            Exchange::Hyperliquid => AssetSymbol::from(market_name),
+            // Perps are named after their base asset, spot markets are `BASE/QUOTE`
+            Exchange::Hyperliquid => market_name.split('/').next().unwrap().into(),
             Exchange::Paradex | Exchange::Lmax | Exchange::Extended => {
                 market_name.split('-').next().unwrap().into()
             }
             Exchange::Kraken => {
                 if market_name.starts_with("PF_") && market_name.ends_with("USD") {
-                    let base_part = &market_name[3..market_name.len() - 3];
-                    match base_part {
-                        "XBT" => "BTC".into(),
-                        other => other.into(),
-                    }
+                    self.normalize_asset_symbol(&market_name[3..market_name.len() - 3])
                 } else {
                     market_name.split('/').next().unwrap().into()
                 }
@@ -84,6 +85,75 @@ impl Exchange {
         }
     }
 
+    /// Returns the quote asset of the market `market_name`, `None` if the market
+    /// name is not in the venue format
+    pub fn quote_symbol_from_raw_market_name(
+        &self,
+        market_name: &RawMarketName,
+    ) -> Option<AssetSymbol> {
+        match self {
+            Exchange::Hyperliquid => match market_name.split_once('/') {
+                Some((_, quote)) => Some(quote.into()),
+                // Perps are quoted in USD
+                None => Some("USD".into()),
+            },
+            Exchange::Paradex => market_name
+                .strip_suffix("-PERP")?
+                .split_once('-')
+                .map(|(_, quote)| quote.into()),
+            Exchange::Lmax | Exchange::Extended => {
+                market_name.split_once('-').map(|(_, quote)| quote.into())
+            }
+            Exchange::Kraken => {
+                if market_name.starts_with("PF_") && market_name.ends_with("USD") {
+                    Some("USD".into())
+                } else {
+                    market_name.split_once('/').map(|(_, quote)| quote.into())
+                }
+            }
+        }
+    }
+
+    /// Maps a venue specific ticker to its canonical asset symbol, e.g `XBT` to
+    /// `BTC` on Kraken. Other symbols are returned unchanged.
+    ///
+    /// Note that Hyperliquid `k` assets are 1000x the canonical one, so `kPEPE`
+    /// becomes `PEPE` but prices & sizes have to be scaled by the caller, see
+    /// [`Exchange::asset_multiplier`].
+    pub fn normalize_asset_symbol(&self, raw: &str) -> AssetSymbol {
+        match self {
+            Exchange::Kraken if raw == "XBT" => "BTC".into(),
+            Exchange::Hyperliquid => match raw.strip_prefix('k') {
+                Some(asset) if HYPERLIQUID_KILO_ASSETS.contains(&asset) => asset.into(),
+                _ => raw.into(),
+            },
+            _ => raw.into(),
+        }
+    }
+
+    /// Number of canonical units one unit of the venue ticker `raw` stands for,
+    /// e.g 1000 for `kPEPE` on Hyperliquid, 1 for most tickers.
+    pub fn asset_multiplier(&self, raw: &str) -> u32 {
+        match self {
+            Exchange::Hyperliquid => match raw.strip_prefix('k') {
+                Some(asset) if HYPERLIQUID_KILO_ASSETS.contains(&asset) => 1000,
+                _ => 1,
+            },
+            _ => 1,
+        }
+    }
+
+    /// Reverse of [`Exchange::normalize_asset_symbol`], e.g `BTC` to `XBT` on Kraken.
+    pub fn denormalize_asset_symbol(&self, asset_symbol: &str) -> String {
+        match self {
+            Exchange::Kraken if asset_symbol == "BTC" => "XBT".into(),
+            Exchange::Hyperliquid if HYPERLIQUID_KILO_ASSETS.contains(&asset_symbol) => {
+                format!("k{asset_symbol}")
+            }
+            _ => asset_symbol.into(),
+        }
+    }
+
     /// Returns the taker fees as a percentage
     /// e.g 0.00045 = 0.045%
     pub const fn taker_fees_rate(&self) -> f64 {
@@ -97,17 +167,85 @@ impl Exchange {
         }
     }
 
+    /// Returns the production REST API base URL, `None` if not known
+    /// Kraken URLs target Kraken Futures, matching `market_name_from_pair`
+    pub const fn rest_base_url(&self) -> Option<&'static str> {
+        match self {
+            // https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api
+            Exchange::Hyperliquid => Some("https://api.hyperliquid.xyz"),
+            // https://docs.paradex.trade/
+            Exchange::Paradex => Some("https://api.prod.paradex.trade/v1"),
+            // https://docs.futures.kraken.com/
+            Exchange::Kraken => Some("https://futures.kraken.com/derivatives/api/v3"),
+            // https://api.docs.extended.exchange/
+            Exchange::Extended => Some("https://api.starknet.extended.exchange/api/v1"),
+            Exchange::Lmax => None,
+        }
+    }
+
+    /// Returns the production WebSocket API base URL, `None` if not known
+    pub const fn ws_base_url(&self) -> Option<&'static str> {
+        match self {
+            // https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/websocket
+            Exchange::Hyperliquid => Some("wss://api.hyperliquid.xyz/ws"),
+            // https://docs.paradex.trade/
+            Exchange::Paradex => Some("wss://ws.api.prod.paradex.trade/v1"),
+            // https://docs.futures.kraken.com/
+            Exchange::Kraken => Some("wss://futures.kraken.com/ws/v1"),
+            // https://api.docs.extended.exchange/
+            Exchange::Extended => {
+                Some("wss://api.starknet.extended.exchange/stream.extended.exchange/v1")
+            }
+            Exchange::Lmax => None,
+        }
+    }
+
+    /// Returns the instrument types available through the endpoints we integrate
+    /// Kraken URLs target Kraken Futures, so only perps are listed
+    pub const fn supported_instruments(&self) -> &'static [InstrumentType] {
+        match self {
+            // https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint
+            Exchange::Hyperliquid => &[InstrumentType::Perp, InstrumentType::Spot],
+            // https://docs.paradex.trade/, https://docs.futures.kraken.com/ &
+            // https://api.docs.extended.exchange/
+            Exchange::Paradex | Exchange::Kraken | Exchange::Extended => &[InstrumentType::Perp],
+            // Spot FX only, https://www.lmax.com/
+            Exchange::Lmax => &[InstrumentType::Spot],
+        }
+    }
+
     /// Whether the exchange has some kind of set leverage endpoint
     pub const fn supports_leverage(&self) -> bool {
+        self.default_max_leverage().is_some()
+    }
+
+    /// Returns the default max leverage of the venue's markets, `None` if leverage can't be set
+    pub const fn default_max_leverage(&self) -> Option<f64> {
         match self {
-            Exchange::Hyperliquid => true,
-            Exchange::Paradex => true,
-            Exchange::Kraken => false,
-            Exchange::Extended => true,
-            Exchange::Lmax => false,
+            Exchange::Hyperliquid => Some(10.0), // https://hyperliquid.gitbook.io/hyperliquid-docs/trading/contract-specifications
+            Exchange::Paradex => Some(20.0),     // https://docs.paradex.trade/
+            Exchange::Extended => Some(20.0),    // https://docs.extended.exchange/
+            Exchange::Kraken | Exchange::Lmax => None,
         }
     }
 
+    /// Returns the max leverage for the market `pair`, `None` if leverage can't be set
+    ///
+    /// Majors get their own caps, other markets fall back to the venue default.
+    /// Venues tier leverage by position size, so this is an upper bound.
+    pub fn max_leverage(&self, pair: &Pair) -> Option<f64> {
+        let default = self.default_max_leverage()?;
+        let max_leverage = match (self, pair.base.as_str()) {
+            // https://hyperliquid.gitbook.io/hyperliquid-docs/trading/contract-specifications
+            (Exchange::Hyperliquid, "BTC") => 40.0,
+            (Exchange::Hyperliquid, "ETH") => 25.0,
+            // https://docs.paradex.trade/ & https://docs.extended.exchange/
+            (Exchange::Paradex | Exchange::Extended, "BTC" | "ETH") => 50.0,
+            _ => default,
+        };
+        Some(max_leverage)
+    }
+
     pub const fn from_str_const(s: &str) -> Option<Self> {
         match s.as_bytes() {
             b"Lmax" | b"lmax" | b"LMAX" => Some(Exchange::Lmax),
@@ -119,3 +257,107 @@ impl Exchange {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Exchange::Hyperliquid, "BTC/USD", Some(40.0))]
+    #[case(Exchange::Hyperliquid, "DOGE/USD", Some(10.0))]
+    #[case(Exchange::Paradex, "BTC/USD", Some(50.0))]
+    #[case(Exchange::Paradex, "SOL/USD", Some(20.0))]
+    #[case(Exchange::Extended, "ETH/USD", Some(50.0))]
+    #[case(Exchange::Kraken, "BTC/USD", None)]
+    #[case(Exchange::Lmax, "EUR/USD", None)]
+    fn test_max_leverage(
+        #[case] exchange: Exchange,
+        #[case] pair: &str,
+        #[case] expected: Option<f64>,
+    ) {
+        let pair = Pair::try_from(pair).unwrap();
+        assert_eq!(exchange.max_leverage(&pair), expected);
+        assert_eq!(exchange.supports_leverage(), expected.is_some());
+    }
+
+    #[rstest]
+    #[case(Exchange::Kraken, &[InstrumentType::Perp])]
+    #[case(Exchange::Hyperliquid, &[InstrumentType::Perp, InstrumentType::Spot])]
+    #[case(Exchange::Lmax, &[InstrumentType::Spot])]
+    fn test_supported_instruments(#[case] exchange: Exchange, #[case] expected: &[InstrumentType]) {
+        assert_eq!(exchange.supported_instruments(), expected);
+    }
+
+    #[rstest]
+    #[case(Exchange::Kraken, "PF_XBTUSD", "BTC", Some("USD"))]
+    #[case(Exchange::Kraken, "PF_XBTEUR", "PF_XBTEUR", None)]
+    #[case(Exchange::Paradex, "ETH-USD-PERP", "ETH", Some("USD"))]
+    #[case(Exchange::Hyperliquid, "BTC", "BTC", Some("USD"))]
+    #[case(Exchange::Hyperliquid, "PURR/USDC", "PURR", Some("USDC"))]
+    #[case(Exchange::Lmax, "EUR-GBP", "EUR", Some("GBP"))]
+    fn test_raw_market_name_parts(
+        #[case] exchange: Exchange,
+        #[case] market_name: &str,
+        #[case] base: &str,
+        #[case] quote: Option<&str>,
+    ) {
+        let market_name = market_name.to_string();
+        assert_eq!(
+            exchange.asset_symbol_from_raw_market_name(&market_name),
+            base
+        );
+        assert_eq!(
+            exchange
+                .quote_symbol_from_raw_market_name(&market_name)
+                .as_deref(),
+            quote
+        );
+    }
+
+    #[rstest]
+    #[case(Exchange::Kraken, "XBT", "BTC")]
+    #[case(Exchange::Kraken, "ETH", "ETH")]
+    #[case(Exchange::Hyperliquid, "kPEPE", "PEPE")]
+    #[case(Exchange::Hyperliquid, "kBTC", "kBTC")]
+    #[case(Exchange::Paradex, "XBT", "XBT")]
+    fn test_normalize_asset_symbol(
+        #[case] exchange: Exchange,
+        #[case] raw: &str,
+        #[case] expected: &str,
+    ) {
+        let normalized = exchange.normalize_asset_symbol(raw);
+        assert_eq!(normalized, expected);
+        assert_eq!(exchange.denormalize_asset_symbol(&normalized), raw);
+    }
+
+    #[rstest]
+    #[case(Exchange::Hyperliquid, "kPEPE", 1000)]
+    #[case(Exchange::Hyperliquid, "PEPE", 1)]
+    #[case(Exchange::Hyperliquid, "kBTC", 1)]
+    #[case(Exchange::Kraken, "kPEPE", 1)]
+    fn test_asset_multiplier(#[case] exchange: Exchange, #[case] raw: &str, #[case] expected: u32) {
+        assert_eq!(exchange.asset_multiplier(raw), expected);
+    }
+
+    #[test]
+    fn test_base_urls_are_valid() {
+        use strum::IntoEnumIterator;
+
+        for exchange in Exchange::iter() {
+            if let Some(rest_url) = exchange.rest_base_url() {
+                let url = url::Url::parse(rest_url).unwrap();
+                assert_eq!(url.scheme(), "https", "{exchange}");
+            }
+            if let Some(ws_url) = exchange.ws_base_url() {
+                let url = url::Url::parse(ws_url).unwrap();
+                assert_eq!(url.scheme(), "wss", "{exchange}");
+            }
+            assert_eq!(
+                exchange.rest_base_url().is_some(),
+                exchange.ws_base_url().is_some(),
+                "{exchange}"
+            );
+        }
+    }
+}
@@ -4,13 +4,23 @@ pub enum InstrumentTypeError {
     Unknown,
 }
 
+/// The type of instrument traded, e.g SPOT or PERP
+///
+/// Parsing (`FromStr`, `TryFrom<&str>`) and deserialization are case-insensitive:
+/// `"spot"`, `"Spot"` and `"sPoT"` are all accepted, unknown inputs fail with
+/// [`InstrumentTypeError::Unknown`].
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, strum::EnumString, strum::Display)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize,))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(
     feature = "borsh",
     derive(borsh::BorshSerialize, borsh::BorshDeserialize)
 )]
-#[strum(ascii_case_insensitive, serialize_all = "UPPERCASE")]
+#[strum(
+    ascii_case_insensitive,
+    serialize_all = "UPPERCASE",
+    parse_err_ty = InstrumentTypeError,
+    parse_err_fn = unknown_instrument_type
+)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub enum InstrumentType {
     #[default]
@@ -55,6 +65,19 @@ impl InstrumentType {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for InstrumentType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| serde::de::Error::custom(format!("Unknown instrument_type: {s}")))
+    }
+}
+
+const fn unknown_instrument_type(_: &str) -> InstrumentTypeError {
+    InstrumentTypeError::Unknown
+}
+
 impl TryFrom<i32> for InstrumentType {
     type Error = InstrumentTypeError;
     fn try_from(value: i32) -> Result<Self, Self::Error> {
@@ -65,3 +88,46 @@ impl TryFrom<i32> for InstrumentType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::rstest]
+    #[case("spot", InstrumentType::Spot)]
+    #[case("SPOT", InstrumentType::Spot)]
+    #[case("Spot", InstrumentType::Spot)]
+    #[case("sPoT", InstrumentType::Spot)]
+    #[case("pErP", InstrumentType::Perp)]
+    fn test_try_from_str(#[case] input: &str, #[case] expected: InstrumentType) {
+        assert_eq!(InstrumentType::try_from(input).unwrap(), expected);
+    }
+
+    #[rstest::rstest]
+    #[case("")]
+    #[case("future")]
+    #[case(" spot")]
+    fn test_try_from_str_unknown(#[case] input: &str) {
+        assert!(matches!(
+            InstrumentType::try_from(input),
+            Err(InstrumentTypeError::Unknown)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case("\"Spot\"", InstrumentType::Spot)]
+    #[case("\"SPOT\"", InstrumentType::Spot)]
+    #[case("\"spot\"", InstrumentType::Spot)]
+    #[case("\"pErP\"", InstrumentType::Perp)]
+    fn test_deserialize_case_insensitive(#[case] input: &str, #[case] expected: InstrumentType) {
+        let instrument_type: InstrumentType = serde_json::from_str(input).unwrap();
+        assert_eq!(instrument_type, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_unknown() {
+        assert!(serde_json::from_str::<InstrumentType>("\"future\"").is_err());
+    }
+}
@@ -62,7 +62,7 @@ impl Interval {
             Self::FifteenMinutes => 15,
             Self::OneHour => 60,
             Self::TwoHours => 120,
-            Self::OneDay => 1400,
+            Self::OneDay => 1440,
             Self::OneWeek => 10080,
         }
     }
@@ -90,6 +90,25 @@ impl Interval {
 
         (self.to_seconds() * 1000) as u64
     }
+
+    /// Returns the number of whole intervals in `[start_ms, end_ms)`, or 0 if
+    /// `end_ms < start_ms`.
+    pub const fn count_between(&self, start_ms: i64, end_ms: i64) -> i64 {
+        if end_ms < start_ms {
+            return 0;
+        }
+        // In i128 as the span of two i64 may not fit an i64
+        ((end_ms as i128 - start_ms as i128) / self.to_millis() as i128) as i64
+    }
+
+    /// Returns the start timestamp (in milliseconds) of every whole interval in
+    /// `[start_ms, end_ms)`.
+    pub fn buckets(&self, start_ms: i64, end_ms: i64) -> Vec<i64> {
+        let step = self.to_millis() as i128;
+        (0..self.count_between(start_ms, end_ms))
+            .map(|i| (start_ms as i128 + i as i128 * step) as i64)
+            .collect()
+    }
 }
 
 impl From<Interval> for Duration {
@@ -97,3 +116,62 @@ impl From<Interval> for Duration {
         Self::from_millis(interval.to_millis())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+    const WEEK_MS: i64 = 7 * DAY_MS;
+
+    #[rstest]
+    #[case(Interval::OneHundredMillisecond, 100, 6_048_000)]
+    #[case(Interval::OneSecond, 1_000, 604_800)]
+    #[case(Interval::FiveSeconds, 5_000, 120_960)]
+    #[case(Interval::TenSeconds, 10_000, 60_480)]
+    #[case(Interval::OneMinute, 60_000, 10_080)]
+    #[case(Interval::FiveMinutes, 300_000, 2_016)]
+    #[case(Interval::FifteenMinutes, 900_000, 672)]
+    #[case(Interval::OneHour, 3_600_000, 168)]
+    #[case(Interval::TwoHours, 7_200_000, 84)]
+    #[case(Interval::OneDay, 86_400_000, 7)]
+    #[case(Interval::OneWeek, 604_800_000, 1)]
+    fn test_interval_in_a_week(
+        #[case] interval: Interval,
+        #[case] millis: u64,
+        #[case] per_week: i64,
+    ) {
+        assert_eq!(interval.to_millis(), millis);
+        assert_eq!(interval.count_between(0, WEEK_MS), per_week);
+        assert_eq!(interval.count_between(0, WEEK_MS - 1), per_week - 1);
+        assert_eq!(interval.count_between(WEEK_MS, 0), 0);
+
+        let start = 1_700_000_000_000;
+        let buckets = interval.buckets(start, start + WEEK_MS);
+        assert_eq!(buckets.len() as i64, per_week);
+        assert_eq!(buckets[0], start);
+        assert_eq!(
+            buckets[buckets.len() - 1],
+            start + (per_week - 1) * millis as i64
+        );
+        assert!(interval.buckets(start, start - 1).is_empty());
+    }
+
+    #[test]
+    fn test_count_between_days_in_a_year() {
+        assert_eq!(Interval::OneDay.count_between(0, 365 * DAY_MS), 365);
+        assert_eq!(Interval::OneDay.buckets(0, 365 * DAY_MS).len(), 365);
+    }
+
+    #[test]
+    fn test_count_between_extreme_bounds() {
+        assert_eq!(
+            Interval::OneWeek.count_between(i64::MIN, i64::MAX),
+            30_500_568_904
+        );
+        let buckets = Interval::OneWeek.buckets(i64::MAX - 2 * WEEK_MS, i64::MAX);
+        assert_eq!(buckets, [i64::MAX - 2 * WEEK_MS, i64::MAX - WEEK_MS]);
+    }
+}
@@ -3,6 +3,8 @@ pub mod web3;
 
 // Entries retrieved through different markets.
 // This is the data that we'll push in our internal Kafka.
+// Entries are also persisted with borsh, which encodes fields in declaration order:
+// any field change breaks previously stored data (layouts are pinned in tests/test_borsh.rs).
 pub mod entries;
 
 #[cfg(feature = "starknet")]
@@ -18,7 +20,7 @@ pub use trading::Side;
 
 // Pair
 pub mod pair;
-pub use pair::{AssetSymbol, Pair, RawMarketName};
+pub use pair::{AssetSymbol, Pair, PairError, RawMarketName};
 
 // Contracts
 pub mod contract;
@@ -61,6 +63,15 @@ pub mod schema {
 #[cfg(feature = "proto")]
 pub trait ProtoSerialize {
     fn to_proto_bytes(&self) -> Vec<u8>;
+
+    /// Same as `to_proto_bytes` but consumes `self`, so fields can be moved
+    /// into the protobuf message instead of being cloned.
+    fn into_proto_bytes(self) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        self.to_proto_bytes()
+    }
 }
 
 // Used to deserialize a protobuf payload into a struct.
@@ -69,4 +80,61 @@ pub trait ProtoDeserialize {
     fn from_proto_bytes(bytes: &[u8]) -> Result<Self, prost::DecodeError>
     where
         Self: Sized;
+
+    /// Same as `from_proto_bytes` but rejects payloads exceeding `limits`, to use
+    /// when decoding untrusted bytes.
+    fn from_proto_bytes_limited(
+        bytes: &[u8],
+        limits: DecodeLimits,
+    ) -> Result<Self, prost::DecodeError>
+    where
+        Self: Sized,
+    {
+        limits.check_message_size(bytes)?;
+        Self::from_proto_bytes(bytes)
+    }
+}
+
+/// Limits enforced by [`ProtoDeserialize::from_proto_bytes_limited`].
+#[cfg(feature = "proto")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// Maximum size of the payload, in bytes
+    pub max_message_size: usize,
+    /// Maximum number of levels on each side of an orderbook
+    pub max_levels: usize,
+}
+
+#[cfg(feature = "proto")]
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_message_size: 4 * 1024 * 1024,
+            max_levels: 10_000,
+        }
+    }
+}
+
+#[cfg(feature = "proto")]
+impl DecodeLimits {
+    pub(crate) fn check_message_size(&self, bytes: &[u8]) -> Result<(), prost::DecodeError> {
+        if bytes.len() > self.max_message_size {
+            return Err(prost::DecodeError::new(format!(
+                "Message of {} bytes exceeds the limit of {} bytes",
+                bytes.len(),
+                self.max_message_size
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_levels(&self, side: &str, levels: usize) -> Result<(), prost::DecodeError> {
+        if levels > self.max_levels {
+            return Err(prost::DecodeError::new(format!(
+                "{levels} {side} exceed the limit of {} levels",
+                self.max_levels
+            )));
+        }
+        Ok(())
+    }
 }
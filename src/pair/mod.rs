@@ -1,6 +1,8 @@
 use std::str::FromStr;
 
-use crate::instrument_type::InstrumentType;
+use anyhow::Context;
+
+use crate::{instrument_type::InstrumentType, Exchange};
 
 const STABLE_SUFFIXES: [&str; 4] = ["USDT", "USDC", "USD", "DAI"];
 
@@ -60,6 +62,49 @@ impl Pair {
         None
     }
 
+    /// Parses a raw market name of `venue` into its canonical pair.
+    ///
+    /// Venue tickers are normalized and stable quotes mapped to USD, e.g
+    /// `PF_XBTUSD` on Kraken -> BTC/USD, `ETH-USD-PERP` on Paradex -> ETH/USD.
+    ///
+    /// Markets of scaled assets, like `kPEPE` on Hyperliquid which is priced per
+    /// 1000 PEPE, are rejected as their prices don't match the canonical pair.
+    pub fn parse_market(venue: &Exchange, raw: &str) -> anyhow::Result<Self> {
+        let market_name: RawMarketName = raw.trim().to_string();
+        let raw_base = venue.asset_symbol_from_raw_market_name(&market_name);
+        let base = venue.normalize_asset_symbol(&raw_base);
+        let multiplier = venue.asset_multiplier(&raw_base);
+        anyhow::ensure!(
+            multiplier == 1,
+            "{venue} market {raw} is priced per {multiplier} {base}"
+        );
+        let quote = venue
+            .quote_symbol_from_raw_market_name(&market_name)
+            .with_context(|| format!("Unknown quote asset in {venue} market {raw}"))?;
+        let mut pair = Self::from_components(&base, &quote)
+            .with_context(|| format!("Invalid {venue} market {raw}"))?;
+
+        // Stable quotes are mapped to USD
+        if STABLE_SUFFIXES.contains(&pair.quote.as_str()) {
+            pair.quote = "USD".to_string();
+        }
+        Ok(pair)
+    }
+
+    /// Parses every input, collecting the parsed pairs and the errors with
+    /// the index of their input instead of stopping at the first failure.
+    pub fn parse_many(inputs: &[&str]) -> (Vec<Self>, Vec<(usize, PairError)>) {
+        let mut pairs = Vec::with_capacity(inputs.len());
+        let mut errors = Vec::new();
+        for (i, input) in inputs.iter().enumerate() {
+            match Self::try_from(*input) {
+                Ok(pair) => pairs.push(pair),
+                Err(error) => errors.push((i, error)),
+            }
+        }
+        (pairs, errors)
+    }
+
     /// Get the base and quote as a tuple
     pub fn as_tuple(&self) -> (AssetSymbol, AssetSymbol) {
         (self.base.clone(), self.quote.clone())
@@ -70,11 +115,44 @@ impl Pair {
         format!("{}{}{}", self.base, separator, self.quote)
     }
 
+    /// Format pair with a custom separator, optionally lowercasing the symbols
+    pub fn format_with_separator_case(&self, separator: &str, lowercase: bool) -> String {
+        let formatted = self.format_with_separator(separator);
+        if lowercase {
+            formatted.to_lowercase()
+        } else {
+            formatted
+        }
+    }
+
     /// Get the pair ID in standard format without consuming self
     pub fn to_pair_id(&self) -> String {
         self.format_with_separator("/")
     }
 
+    /// Get the pair ID in lowercase, e.g `btc/usd`
+    pub fn to_pair_id_lowercase(&self) -> String {
+        self.format_with_separator_case("/", true)
+    }
+
+    /// Whether `symbol` (case-insensitive) is either the base or the quote of the pair
+    pub fn contains(&self, symbol: &str) -> bool {
+        self.other_side(symbol).is_some()
+    }
+
+    /// Returns the counterpart asset of `symbol` (case-insensitive), `None` if
+    /// `symbol` is not part of the pair
+    pub fn other_side(&self, symbol: &str) -> Option<&str> {
+        let symbol = symbol.to_uppercase();
+        if symbol == self.base {
+            Some(&self.quote)
+        } else if symbol == self.quote {
+            Some(&self.base)
+        } else {
+            None
+        }
+    }
+
     /// Get the market ID in unified format: BASE:QUOTE:TYPE
     /// Used for ClickHouse joins across different data sources
     /// instrument_type is formatted in UPPERCASE (SPOT, PERP)
@@ -99,48 +177,78 @@ impl From<Pair> for String {
     }
 }
 
-impl TryFrom<&str> for Pair {
-    type Error = anyhow::Error;
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PairError {
+    #[error("Invalid pair format: expected format like A/B, got {0}")]
+    InvalidFormat(String),
+    #[error("Invalid pair format: base and quote must not be empty")]
+    EmptyComponent,
+    #[error("Invalid pair symbol: unsupported characters in {0}")]
+    NonAlphanumeric(String),
+}
 
-    fn try_from(pair_id: &str) -> anyhow::Result<Self> {
-        // Normalize: replace "-" and "_" with "/"
-        let normalized = pair_id.replace(['-', '_'], "/");
+impl Pair {
+    /// Builds a pair from raw base & quote components, validating them.
+    fn from_components(base: &str, quote: &str) -> Result<Self, PairError> {
+        let (base, quote) = (base.trim(), quote.trim());
 
-        // Split into parts
-        let parts: Vec<&str> = normalized.split('/').collect();
+        if base.is_empty() || quote.is_empty() {
+            return Err(PairError::EmptyComponent);
+        }
 
-        // Validate: exactly 2 parts
-        if parts.len() != 2 || parts[0].trim().is_empty() || parts[1].trim().is_empty() {
-            anyhow::bail!("Invalid pair format: expected format like A/B");
+        for symbol in [base, quote] {
+            if !symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(PairError::NonAlphanumeric(symbol.to_string()));
+            }
         }
 
         Ok(Self {
-            base: parts[0].trim().to_uppercase(),
-            quote: parts[1].trim().to_uppercase(),
+            base: base.to_uppercase(),
+            quote: quote.to_uppercase(),
         })
     }
 }
 
+impl TryFrom<&str> for Pair {
+    type Error = PairError;
+
+    fn try_from(pair_id: &str) -> Result<Self, Self::Error> {
+        // Normalize: replace "-" and "_" with "/"
+        let normalized = pair_id.replace(['-', '_'], "/");
+
+        // Validate: exactly 2 non-empty parts, symbols themselves are not checked
+        match normalized.split('/').collect::<Vec<&str>>().as_slice() {
+            [base, quote] if base.trim().is_empty() || quote.trim().is_empty() => {
+                Err(PairError::EmptyComponent)
+            }
+            [base, quote] => Ok(Self {
+                base: base.trim().to_uppercase(),
+                quote: quote.trim().to_uppercase(),
+            }),
+            _ => Err(PairError::InvalidFormat(pair_id.to_string())),
+        }
+    }
+}
+
 impl TryFrom<String> for Pair {
-    type Error = anyhow::Error;
+    type Error = PairError;
 
-    fn try_from(pair_id: String) -> anyhow::Result<Self> {
+    fn try_from(pair_id: String) -> Result<Self, Self::Error> {
         Self::try_from(pair_id.as_str())
     }
 }
 
 impl TryFrom<(String, String)> for Pair {
-    type Error = anyhow::Error;
+    type Error = PairError;
 
-    fn try_from(pair: (String, String)) -> anyhow::Result<Self> {
+    /// Unlike pair ids, components are not trimmed and may only hold ASCII letters.
+    fn try_from(pair: (String, String)) -> Result<Self, Self::Error> {
         let (base, quote) = pair;
 
-        if !base.chars().all(|c| c.is_ascii_alphabetic()) {
-            anyhow::bail!("Invalid base symbol: only ASCII letters allowed");
-        }
-
-        if !quote.chars().all(|c| c.is_ascii_alphabetic()) {
-            anyhow::bail!("Invalid quote symbol: only ASCII letters allowed");
+        for symbol in [&base, &quote] {
+            if !symbol.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(PairError::NonAlphanumeric(symbol.clone()));
+            }
         }
 
         Ok(Self {
@@ -151,13 +259,75 @@ impl TryFrom<(String, String)> for Pair {
 }
 
 impl FromStr for Pair {
-    type Err = anyhow::Error;
+    type Err = PairError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::try_from(s)
     }
 }
 
+/// Serde helpers to use `Pair` as a map key, e.g. `HashMap<Pair, T>`.
+///
+/// Pairs are (de)serialized as their pair id, i.e `"BTC/USD"`:
+/// ```
+/// use std::collections::HashMap;
+///
+/// use pragma_common::Pair;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Prices {
+///     #[serde(with = "pragma_common::pair::as_map_key")]
+///     prices: HashMap<Pair, f64>,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod as_map_key {
+    use std::collections::HashMap;
+    use std::hash::BuildHasher;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Pair;
+
+    pub fn serialize<V, H, S>(map: &HashMap<Pair, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_map(map.iter().map(|(pair, value)| (pair.to_pair_id(), value)))
+    }
+
+    pub fn deserialize<'de, V, H, D>(deserializer: D) -> Result<HashMap<Pair, V, H>, D::Error>
+    where
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+        D: Deserializer<'de>,
+    {
+        HashMap::<String, V>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(pair_id, value)| {
+                Pair::try_from(pair_id.as_str())
+                    .map(|pair| (pair, value))
+                    .map_err(D::Error::custom)
+            })
+            .collect()
+    }
+}
+
+/// Builds a [`Pair`] from a literal, validated at compile time.
+///
+/// ```
+/// let pair = pragma_common::pair!("btc/usd");
+/// assert_eq!(pair.to_pair_id(), "BTC/USD");
+/// ```
+///
+/// Both sides of the separator must be non-empty:
+/// ```compile_fail
+/// let pair = pragma_common::pair!("/USD");
+/// ```
+/// ```compile_fail
+/// let pair = pragma_common::pair!("BTC/");
+/// ```
 #[macro_export]
 macro_rules! pair {
     ($pair_str:expr) => {{
@@ -166,20 +336,28 @@ macro_rules! pair {
         const fn is_valid_pair(s: &str) -> bool {
             let bytes = s.as_bytes();
             let mut count = 0;
+            let mut base_len = 0;
+            let mut quote_len = 0;
             let mut i = 0;
             while i < bytes.len() {
                 if bytes[i] == b'/' || bytes[i] == b'-' || bytes[i] == b'_' {
                     count += 1;
+                } else if bytes[i] != b' ' {
+                    if count == 0 {
+                        base_len += 1;
+                    } else {
+                        quote_len += 1;
+                    }
                 }
                 i += 1;
             }
-            count == 1
+            count == 1 && base_len > 0 && quote_len > 0
         }
 
         const _: () = {
             assert!(
                 is_valid_pair($pair_str),
-                "Invalid pair format. Expected format: BASE/QUOTE, BASE-QUOTE, or BASE_QUOTE"
+                "Invalid pair format. Expected format: BASE/QUOTE, BASE-QUOTE, or BASE_QUOTE with non-empty BASE and QUOTE"
             );
         };
 
@@ -198,6 +376,30 @@ mod tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    #[case(Exchange::Kraken, "PF_XBTUSD", "BTC/USD")]
+    #[case(Exchange::Kraken, "PF_ETHUSD", "ETH/USD")]
+    #[case(Exchange::Paradex, "ETH-USD-PERP", "ETH/USD")]
+    #[case(Exchange::Hyperliquid, "PEPE", "PEPE/USD")]
+    #[case(Exchange::Hyperliquid, "PURR/USDC", "PURR/USD")]
+    #[case(Exchange::Hyperliquid, "USDE/USDT", "USDE/USD")]
+    #[case(Exchange::Lmax, "EUR-GBP", "EUR/GBP")]
+    fn test_parse_market(#[case] venue: Exchange, #[case] raw: &str, #[case] expected: &str) {
+        assert_eq!(
+            Pair::parse_market(&venue, raw).unwrap(),
+            Pair::try_from(expected).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(Exchange::Kraken, "PF_XBTEUR")]
+    #[case(Exchange::Paradex, "ETH-USD-FUTURE")]
+    #[case(Exchange::Extended, "")]
+    #[case(Exchange::Hyperliquid, "kPEPE")]
+    fn test_parse_market_invalid(#[case] venue: Exchange, #[case] raw: &str) {
+        assert!(Pair::parse_market(&venue, raw).is_err());
+    }
+
     /// Test `from_stable_pair` with various inputs
     #[rstest]
     #[case("BTCUSDT", Some(Pair { base: "BTC".to_string(), quote: "USD".to_string() }))]
@@ -266,6 +468,41 @@ mod tests {
         assert_eq!(pair.format_with_separator(separator), expected);
     }
 
+    /// Test `format_with_separator_case` only changes the output case
+    #[rstest]
+    #[case("/", true, "btc/usd")]
+    #[case("-", true, "btc-usd")]
+    #[case("-", false, "BTC-USD")]
+    fn test_format_with_separator_case(
+        #[case] separator: &str,
+        #[case] lowercase: bool,
+        #[case] expected: &str,
+    ) {
+        let pair = Pair::from_currencies("btc", "usd");
+        assert_eq!(
+            pair.format_with_separator_case(separator, lowercase),
+            expected
+        );
+        assert_eq!(pair.base, "BTC");
+    }
+
+    #[test]
+    fn test_to_pair_id_lowercase() {
+        let pair = Pair::from_currencies("BTC", "USD");
+        assert_eq!(pair.to_pair_id_lowercase(), "btc/usd");
+    }
+
+    #[test]
+    fn test_contains_and_other_side() {
+        let pair = Pair::from_currencies("BTC", "USD");
+        assert!(pair.contains("usd"));
+        assert!(pair.contains("BTC"));
+        assert!(!pair.contains("ETH"));
+        assert_eq!(pair.other_side("btc"), Some("USD"));
+        assert_eq!(pair.other_side("Usd"), Some("BTC"));
+        assert_eq!(pair.other_side("ETH"), None);
+    }
+
     /// Test `to_pair_id` uses the standard "/" separator
     #[rstest]
     #[case(Pair { base: "BTC".to_string(), quote: "USD".to_string() }, "BTC/USD")]
@@ -329,6 +566,88 @@ mod tests {
         assert_eq!(pair, expected);
     }
 
+    /// Test that malformed pair ids are rejected with the matching `PairError`
+    #[rstest]
+    #[case("BTCUSD", PairError::InvalidFormat("BTCUSD".to_string()))]
+    #[case("BTC/USD/ETH", PairError::InvalidFormat("BTC/USD/ETH".to_string()))]
+    #[case("BTC-USD_ETH", PairError::InvalidFormat("BTC-USD_ETH".to_string()))]
+    #[case("/USD", PairError::EmptyComponent)]
+    #[case("BTC/ ", PairError::EmptyComponent)]
+    fn test_pair_error(#[case] input: &str, #[case] expected: PairError) {
+        assert_eq!(Pair::try_from(input).unwrap_err(), expected);
+        assert_eq!(input.parse::<Pair>().unwrap_err(), expected);
+    }
+
+    /// Test that pair ids don't validate their symbols
+    #[rstest]
+    #[case("BTC.B/USD", "BTC.B", "USD")]
+    #[case("1000PEPE-USDT", "1000PEPE", "USDT")]
+    fn test_pair_id_symbols_not_validated(
+        #[case] input: &str,
+        #[case] base: &str,
+        #[case] quote: &str,
+    ) {
+        assert_eq!(
+            Pair::try_from(input).unwrap(),
+            Pair {
+                base: base.to_string(),
+                quote: quote.to_string()
+            }
+        );
+    }
+
+    /// Test that tuple components may only hold ASCII letters, and may be empty
+    #[rstest]
+    #[case("1000PEPE", "USD", Err(PairError::NonAlphanumeric("1000PEPE".to_string())))]
+    #[case("BTC", " USD", Err(PairError::NonAlphanumeric(" USD".to_string())))]
+    #[case("btc", "usd", Ok(Pair::from_currencies("BTC", "USD")))]
+    #[case("", "USD", Ok(Pair::from_currencies("", "USD")))]
+    fn test_pair_from_tuple(
+        #[case] base: &str,
+        #[case] quote: &str,
+        #[case] expected: Result<Pair, PairError>,
+    ) {
+        assert_eq!(
+            Pair::try_from((base.to_string(), quote.to_string())),
+            expected
+        );
+    }
+
+    /// Test `parse_many` keeps valid pairs and reports every invalid input
+    #[test]
+    fn test_parse_many() {
+        let (pairs, errors) = Pair::parse_many(&["BTC/USD", "ETHUSD", "sol-usdc", "/USD"]);
+
+        assert_eq!(
+            pairs,
+            vec![
+                Pair::from_currencies("BTC", "USD"),
+                Pair::from_currencies("SOL", "USDC")
+            ]
+        );
+        assert_eq!(
+            errors,
+            vec![
+                (1, PairError::InvalidFormat("ETHUSD".to_string())),
+                (3, PairError::EmptyComponent),
+            ]
+        );
+    }
+
+    /// Test that `PairError` still converts into `anyhow::Error` for `?` callers
+    #[test]
+    fn test_pair_error_into_anyhow() {
+        fn parse(input: &str) -> anyhow::Result<Pair> {
+            Ok(input.parse()?)
+        }
+
+        let error = parse("BTCUSD").unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<PairError>(),
+            Some(&PairError::InvalidFormat("BTCUSD".to_string()))
+        );
+    }
+
     /// Test the `pair!` macro with valid inputs
     #[test]
     fn test_pair_macro() {
@@ -360,6 +679,36 @@ mod tests {
                 quote: "USD".to_string()
             }
         );
+        assert_eq!(
+            pair!("A/B"),
+            Pair {
+                base: "A".to_string(),
+                quote: "B".to_string()
+            }
+        );
+    }
+
+    /// Test `as_map_key` serializes pairs as JSON map keys and back
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_as_map_key() {
+        use std::collections::HashMap;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Prices {
+            #[serde(with = "crate::pair::as_map_key")]
+            prices: HashMap<Pair, f64>,
+        }
+
+        let prices = Prices {
+            prices: HashMap::from([(pair!("BTC/USD"), 100_000.0)]),
+        };
+
+        let json = serde_json::to_string(&prices).unwrap();
+        assert_eq!(json, r#"{"prices":{"BTC/USD":100000.0}}"#);
+
+        let decoded: Prices = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, prices);
     }
 
     /// Test the `Default` implementation
@@ -1,11 +1,18 @@
 /// Inspiration from:
 /// <https://github.com/madara-alliance/madara/blob/main/crates/madara/primitives/utils/src/service.rs>
-use std::{panic, time::Duration};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use anyhow::{anyhow, Context};
-use futures::Future;
+use futures::{Future, FutureExt};
 use tokio::task::JoinSet;
-use tokio_util::sync::CancellationToken;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 
 /// Maximum duration a service is allowed to take to shutdown, after which it
 /// will be forcefully cancelled
@@ -40,6 +47,11 @@ impl ServiceContext {
         self.token.is_cancelled()
     }
 
+    /// Returns a future that completes once this context is cancelled
+    pub fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.token.cancelled()
+    }
+
     /// Runs a future until the service is cancelled
     pub async fn run_until_cancelled<T, F>(&self, f: F) -> Option<T>
     where
@@ -61,7 +73,18 @@ pub trait Service: 'static + Send + Sync {
         Ok(())
     }
 
-    /// Helper to start and drive a service to completion
+    /// Called once when the service stops, so it can flush its state. Default
+    /// implementation does nothing.
+    ///
+    /// A [`ServiceGroup`] calls it when cancelled, concurrently with the spawned loops
+    /// winding down, and when its services end, including on a critical service
+    /// error or panic. [`Service::start_and_drive_to_end`] calls it once the service
+    /// ended. Hooks are awaited one after the other and abandoned after
+    /// [`SERVICE_GRACE_PERIOD`].
+    async fn on_shutdown(&mut self) {}
+
+    /// Helper to start and drive a service to completion, then call its
+    /// [`Service::on_shutdown`] hook
     async fn start_and_drive_to_end(mut self) -> anyhow::Result<()>
     where
         Self: Sized,
@@ -71,7 +94,11 @@ pub trait Service: 'static + Send + Sync {
         let runner = ServiceRunner::new(ctx, &mut join_set);
 
         self.start(runner).await.context("Starting service")?;
-        drive_critical_joinset(join_set).await
+        let result = AssertUnwindSafe(drive_critical_joinsets(vec![join_set], Arc::default()))
+            .catch_unwind()
+            .await;
+        run_shutdown_hooks(self.on_shutdown()).await;
+        result.unwrap_or_else(|payload| panic::resume_unwind(payload))
     }
 }
 
@@ -104,15 +131,124 @@ impl<'a> ServiceRunner<'a> {
             Ok(())
         });
     }
+
+    /// Spawn a blocking service loop on the blocking thread pool.
+    ///
+    /// Meant for CPU-bound work that shouldn't run on the async runtime. Since
+    /// blocking tasks can't be interrupted, `runner` is responsible for checking
+    /// [`ServiceContext::is_cancelled`] between iterations.
+    pub fn spawn_blocking_loop<E>(
+        &mut self,
+        runner: impl FnOnce(ServiceContext) -> Result<(), E> + Send + 'static,
+    ) where
+        E: Into<anyhow::Error> + Send + 'static,
+    {
+        let ctx = self.ctx.clone();
+        self.join_set.spawn(async move {
+            let runner_ctx = ctx.clone();
+            let handle = tokio::task::spawn_blocking(move || runner(runner_ctx));
+            tokio::select! {
+                res = handle => match res {
+                    Ok(res) => res.map_err(Into::into)?,
+                    Err(join_error) if join_error.is_panic() => {
+                        panic::resume_unwind(join_error.into_panic());
+                    }
+                    Err(_) => {}
+                },
+                () = async {
+                    ctx.token.cancelled().await;
+                    tokio::time::sleep(SERVICE_GRACE_PERIOD).await;
+                } => {}
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Adapter turning a plain async function into a [`Service`]
+///
+/// The function is spawned through [`ServiceRunner::spawn_loop`] on start, so it
+/// can be added to a [`ServiceGroup`] without declaring a dedicated struct.
+pub struct FnService<F> {
+    runner: std::sync::Mutex<Option<F>>,
+}
+
+impl<F, Fut> FnService<F>
+where
+    F: FnOnce(ServiceContext) -> Fut + Send + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    pub fn new(runner: F) -> Self {
+        Self {
+            runner: std::sync::Mutex::new(Some(runner)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<F, Fut> Service for FnService<F>
+where
+    F: FnOnce(ServiceContext) -> Fut + Send + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    async fn start<'a>(&mut self, mut runner: ServiceRunner<'a>) -> anyhow::Result<()> {
+        let f = self
+            .runner
+            .get_mut()
+            .map_err(|_| anyhow!("FnService lock poisoned"))?
+            .take()
+            .context("FnService has already been started")?;
+        runner.spawn_loop(f);
+        Ok(())
+    }
+}
+
+/// Snapshot of the services of a [`ServiceGroup`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServiceGroupStats {
+    pub critical_total: usize,
+    pub auxiliary_total: usize,
+    /// Number of service tasks that returned an error or panicked
+    pub failures: usize,
+}
+
+/// Counters updated by the join set drivers of a started [`ServiceGroup`]
+#[derive(Debug, Default)]
+struct ServiceGroupCounters {
+    running: AtomicUsize,
+    failures: AtomicUsize,
+}
+
+impl ServiceGroupCounters {
+    /// Records the completion of a task, returns true if it failed
+    fn record<T>(&self, result: &Result<anyhow::Result<T>, tokio::task::JoinError>) -> bool {
+        self.running.fetch_sub(1, Ordering::Relaxed);
+        let failed = match result {
+            Ok(result) => result.is_err(),
+            Err(join_error) => join_error.is_panic(),
+        };
+        if failed {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        failed
+    }
+}
+
+/// Service shared between a [`ServiceGroup`] and its shutdown hooks
+type SharedService = Arc<futures::lock::Mutex<Box<dyn Service>>>;
+
+fn shared(service: Box<dyn Service>) -> SharedService {
+    Arc::new(futures::lock::Mutex::new(service))
 }
 
 /// A group of services that can be started together
 #[derive(Default)]
 pub struct ServiceGroup {
-    critical_services: Vec<Box<dyn Service>>,
-    auxiliary_services: Vec<Box<dyn Service>>,
+    critical_services: Vec<SharedService>,
+    auxiliary_services: Vec<SharedService>,
     critical_join_set: Option<JoinSet<anyhow::Result<()>>>,
     auxiliary_join_set: Option<JoinSet<anyhow::Result<()>>>,
+    counters: Arc<ServiceGroupCounters>,
 }
 
 impl ServiceGroup {
@@ -124,8 +260,8 @@ impl ServiceGroup {
         let has_auxiliary_services = !auxiliary_services.is_empty();
 
         Self {
-            critical_services,
-            auxiliary_services,
+            critical_services: critical_services.into_iter().map(shared).collect(),
+            auxiliary_services: auxiliary_services.into_iter().map(shared).collect(),
             critical_join_set: if has_critical_services {
                 Some(JoinSet::default())
             } else {
@@ -136,6 +272,20 @@ impl ServiceGroup {
             } else {
                 None
             },
+            counters: Arc::default(),
+        }
+    }
+
+    /// Number of service tasks of the group still running
+    pub fn running_count(&self) -> usize {
+        self.counters.running.load(Ordering::Relaxed)
+    }
+
+    pub fn stats(&self) -> ServiceGroupStats {
+        ServiceGroupStats {
+            critical_total: self.critical_services.len(),
+            auxiliary_total: self.auxiliary_services.len(),
+            failures: self.counters.failures.load(Ordering::Relaxed),
         }
     }
 
@@ -143,14 +293,14 @@ impl ServiceGroup {
         if self.critical_join_set.is_none() {
             self.critical_join_set = Some(JoinSet::default());
         }
-        self.critical_services.push(Box::new(service));
+        self.critical_services.push(shared(Box::new(service)));
     }
 
     pub fn push_auxiliary(&mut self, service: impl Service) {
         if self.auxiliary_join_set.is_none() {
             self.auxiliary_join_set = Some(JoinSet::default());
         }
-        self.auxiliary_services.push(Box::new(service));
+        self.auxiliary_services.push(shared(Box::new(service)));
     }
 
     #[must_use]
@@ -178,9 +328,11 @@ impl Service for ServiceGroup {
             .take()
             .context("ServiceGroup has already been started")?;
 
-        for service in &mut self.critical_services {
+        for service in &self.critical_services {
             let ctx = runner.ctx.clone();
             service
+                .lock()
+                .await
                 .start(ServiceRunner::new(ctx, &mut own_critical_join_set))
                 .await
                 .context("Starting critical service")?;
@@ -192,34 +344,205 @@ impl Service for ServiceGroup {
                 .take()
                 .context("ServiceGroup has already been started")?;
 
-            for service in &mut self.auxiliary_services {
+            for service in &self.auxiliary_services {
                 let ctx = runner.ctx.clone();
-                // Ignore start result for auxiliary services
-                let _ = service
+                // Auxiliary services failing to start don't stop the group
+                if let Err(error) = service
+                    .lock()
+                    .await
                     .start(ServiceRunner::new(ctx, &mut own_auxiliary_join_set))
-                    .await;
+                    .await
+                {
+                    tracing::warn!("Failed to start auxiliary service: {error:#}");
+                }
             }
 
-            runner.join_set.spawn(drive_critical_and_auxiliary_joinsets(
-                own_critical_join_set,
-                own_auxiliary_join_set,
-            ));
+            self.spawn_driver(
+                runner,
+                vec![own_critical_join_set],
+                vec![own_auxiliary_join_set],
+            );
         } else {
-            runner
-                .join_set
-                .spawn(drive_critical_joinset(own_critical_join_set));
-        };
+            self.spawn_driver(runner, vec![own_critical_join_set], Vec::new());
+        }
 
         Ok(())
     }
 }
 
-async fn drive_critical_joinset(mut join_set: JoinSet<anyhow::Result<()>>) -> anyhow::Result<()> {
-    while let Some(result) = join_set.join_next().await {
+impl ServiceGroup {
+    /// Same as [`Service::start`], but starts the critical services concurrently,
+    /// then the auxiliary ones, so a slow initialization doesn't delay the others.
+    ///
+    /// The first critical service failing to start aborts the group.
+    pub async fn start_concurrent(&mut self, runner: ServiceRunner<'_>) -> anyhow::Result<()> {
+        if self.critical_services.is_empty() {
+            return Err(anyhow!("ServiceGroup started without any critical service"));
+        }
+        self.critical_join_set
+            .take()
+            .context("ServiceGroup has already been started")?;
+        self.auxiliary_join_set.take();
+
+        let critical_join_sets =
+            futures::future::try_join_all(self.critical_services.iter().map(|service| {
+                let ctx = runner.ctx.clone();
+                async move {
+                    let mut join_set = JoinSet::new();
+                    service
+                        .lock()
+                        .await
+                        .start(ServiceRunner::new(ctx, &mut join_set))
+                        .await
+                        .context("Starting critical service")?;
+                    anyhow::Ok(join_set)
+                }
+            }))
+            .await?;
+
+        let auxiliary_join_sets =
+            futures::future::join_all(self.auxiliary_services.iter().map(|service| {
+                let ctx = runner.ctx.clone();
+                async move {
+                    let mut join_set = JoinSet::new();
+                    // Auxiliary services failing to start don't stop the group
+                    if let Err(error) = service
+                        .lock()
+                        .await
+                        .start(ServiceRunner::new(ctx, &mut join_set))
+                        .await
+                    {
+                        tracing::warn!("Failed to start auxiliary service: {error:#}");
+                    }
+                    join_set
+                }
+            }))
+            .await;
+
+        self.spawn_driver(runner, critical_join_sets, auxiliary_join_sets);
+        Ok(())
+    }
+
+    /// Spawns the task driving the started services on `runner`
+    fn spawn_driver(
+        &self,
+        runner: ServiceRunner<'_>,
+        critical_join_sets: Vec<JoinSet<anyhow::Result<()>>>,
+        auxiliary_join_sets: Vec<JoinSet<anyhow::Result<()>>>,
+    ) {
+        let running: usize = critical_join_sets
+            .iter()
+            .chain(&auxiliary_join_sets)
+            .map(JoinSet::len)
+            .sum();
+        self.counters.running.fetch_add(running, Ordering::Relaxed);
+
+        let ctx = runner.ctx.clone();
+        if auxiliary_join_sets.is_empty() {
+            runner.join_set.spawn(drive_with_shutdown_hooks(
+                ctx,
+                self.services(),
+                drive_critical_joinsets(critical_join_sets, self.counters.clone()),
+            ));
+        } else {
+            runner.join_set.spawn(drive_with_shutdown_hooks(
+                ctx,
+                self.services(),
+                drive_critical_and_auxiliary_joinsets(
+                    critical_join_sets,
+                    auxiliary_join_sets,
+                    self.counters.clone(),
+                ),
+            ));
+        }
+    }
+
+    fn services(&self) -> Vec<SharedService> {
+        self.critical_services
+            .iter()
+            .chain(&self.auxiliary_services)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Drives `driver` to completion, calling [`Service::on_shutdown`] on every service
+/// once, either when `ctx` is cancelled or when `driver` ends, even by panicking
+async fn drive_with_shutdown_hooks(
+    ctx: ServiceContext,
+    services: Vec<SharedService>,
+    driver: impl Future<Output = anyhow::Result<()>>,
+) -> anyhow::Result<()> {
+    let hooks = async {
+        for service in &services {
+            service.lock().await.on_shutdown().await;
+        }
+    };
+    let driver = AssertUnwindSafe(driver).catch_unwind();
+    tokio::pin!(driver);
+    let result = tokio::select! {
+        // Services usually stop on cancellation, so the hooks must win the race
+        biased;
+        () = ctx.cancelled() => {
+            run_shutdown_hooks(hooks).await;
+            driver.await
+        }
+        result = &mut driver => {
+            run_shutdown_hooks(hooks).await;
+            result
+        }
+    };
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// Awaits shutdown `hooks`, abandoning them after [`SERVICE_GRACE_PERIOD`]
+async fn run_shutdown_hooks(hooks: impl Future<Output = ()>) {
+    if tokio::time::timeout(SERVICE_GRACE_PERIOD, hooks)
+        .await
+        .is_err()
+    {
+        tracing::warn!("Service shutdown hooks did not complete within the grace period");
+    }
+}
+
+/// Waits for the next task of any of `join_sets`, removing the exhausted sets.
+/// Returns `None` once every set is empty.
+async fn join_next_any<T: 'static>(
+    join_sets: &mut Vec<JoinSet<T>>,
+) -> Option<Result<T, tokio::task::JoinError>> {
+    while !join_sets.is_empty() {
+        let (result, index, _) = futures::future::select_all(
+            join_sets
+                .iter_mut()
+                .map(|join_set| Box::pin(join_set.join_next())),
+        )
+        .await;
+        match result {
+            Some(result) => return Some(result),
+            None => {
+                join_sets.swap_remove(index);
+            }
+        }
+    }
+    None
+}
+
+async fn drive_critical_joinsets(
+    mut join_sets: Vec<JoinSet<anyhow::Result<()>>>,
+    counters: Arc<ServiceGroupCounters>,
+) -> anyhow::Result<()> {
+    while let Some(result) = join_next_any(&mut join_sets).await {
+        if counters.record(&result) {
+            // Remaining tasks are aborted when the join sets are dropped
+            let remaining: usize = join_sets.iter().map(JoinSet::len).sum();
+            counters.running.fetch_sub(remaining, Ordering::Relaxed);
+        }
         match result {
             Ok(result) => result?,
             Err(panic_error) if panic_error.is_panic() => {
-                panic::resume_unwind(panic_error.into_panic());
+                let payload = panic_error.into_panic();
+                tracing::error!("Critical service panicked: {}", panic_message(&*payload));
+                panic::resume_unwind(payload);
             }
             Err(_) => {}
         }
@@ -229,13 +552,24 @@ async fn drive_critical_joinset(mut join_set: JoinSet<anyhow::Result<()>>) -> an
 }
 
 async fn drive_critical_and_auxiliary_joinsets(
-    critical_join_set: JoinSet<anyhow::Result<()>>,
-    mut auxiliary_join_set: JoinSet<anyhow::Result<()>>,
+    critical_join_sets: Vec<JoinSet<anyhow::Result<()>>>,
+    mut auxiliary_join_sets: Vec<JoinSet<anyhow::Result<()>>>,
+    counters: Arc<ServiceGroupCounters>,
 ) -> anyhow::Result<()> {
     let (res_critical, _ret_auxiliary) = futures::future::join(
-        drive_critical_joinset(critical_join_set),
+        drive_critical_joinsets(critical_join_sets, counters.clone()),
         // Ignore result for auxiliary services
-        async { while let Some(_result) = auxiliary_join_set.join_next().await {} },
+        async {
+            while let Some(result) = join_next_any(&mut auxiliary_join_sets).await {
+                counters.record(&result);
+                if let Err(join_error) = result {
+                    if join_error.is_panic() {
+                        let payload = join_error.into_panic();
+                        tracing::error!("Auxiliary service panicked: {}", panic_message(&*payload));
+                    }
+                }
+            }
+        },
     )
     .await;
 
@@ -243,3 +577,12 @@ async fn drive_critical_and_auxiliary_joinsets(
 
     Ok(())
 }
+
+/// Extracts the message of a panic payload, if it is a string
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload")
+}
@@ -1,7 +1,8 @@
 use starknet_rust::core::crypto::EcdsaSignError;
 use starknet_rust::core::types::Felt;
+use starknet_rust::providers::ProviderError;
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize,))]
 #[cfg_attr(
@@ -28,6 +29,16 @@ pub enum ConversionError {
     FailedSignature(String),
 }
 
+/// Error of [`FallbackProvider::wait_for_with_cancel`](super::FallbackProvider::wait_for_with_cancel),
+/// telling a shutdown apart from an RPC failure.
+#[derive(Debug, thiserror::Error)]
+pub enum WaitForError {
+    #[error("cancelled while waiting for the transaction")]
+    Cancelled,
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
 #[derive(Debug, thiserror::Error)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub enum SignerError {
@@ -44,3 +55,77 @@ pub enum SignerError {
     #[error("invalid message: {0}")]
     InvalidMessage(String),
 }
+
+/// Serializable view of a [`SignerError`], safe to return in API responses.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SignerErrorDto {
+    pub code: String,
+    pub message: String,
+}
+
+impl SignerError {
+    /// Stable, machine readable code of the error.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::ConversionError(_) => "conversion_error",
+            Self::SigningError(_) => "signing_error",
+            Self::InvalidSignature(_) => "invalid_signature",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::InvalidMessage(_) => "invalid_message",
+        }
+    }
+}
+
+impl From<&SignerError> for SignerErrorDto {
+    fn from(error: &SignerError) -> Self {
+        Self {
+            code: error.code().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(
+        SignerError::ConversionError(ConversionError::FeltConversion),
+        "conversion_error"
+    )]
+    #[case(
+        SignerError::SigningError(EcdsaSignError::MessageHashOutOfRange),
+        "signing_error"
+    )]
+    #[case(SignerError::InvalidSignature(Felt::from(42_u32)), "invalid_signature")]
+    #[case(SignerError::Unauthorized("nope".to_string()), "unauthorized")]
+    #[case(SignerError::InvalidMessage("bad".to_string()), "invalid_message")]
+    fn test_signer_error_dto(#[case] error: SignerError, #[case] code: &str) {
+        let dto = SignerErrorDto::from(&error);
+        assert_eq!(dto.code, code);
+        assert_eq!(dto.message, error.to_string());
+
+        let json = serde_json::to_string(&dto).unwrap();
+        assert_eq!(serde_json::from_str::<SignerErrorDto>(&json).unwrap(), dto);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_conversion_error_serde_roundtrip() {
+        for error in [
+            ConversionError::FailedSerialization,
+            ConversionError::U128Conversion,
+            ConversionError::FailedSignature("BTC/USD".to_string()),
+        ] {
+            let json = serde_json::to_string(&error).unwrap();
+            assert_eq!(
+                serde_json::from_str::<ConversionError>(&json).unwrap(),
+                error
+            );
+        }
+    }
+}
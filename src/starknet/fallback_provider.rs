@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -23,6 +24,9 @@ use starknet_rust::{
 use tokio::sync::RwLock;
 use tokio::time::{sleep, timeout};
 
+#[cfg(feature = "services")]
+use super::errors::WaitForError;
+
 /// Target status for waiting on transaction finality
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WaitForTarget {
@@ -60,30 +64,109 @@ pub enum WaitForTarget {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct FallbackProvider {
     /// List of providers sorted by priority (index 0 = highest priority)
     providers: Vec<JsonRpcClient<HttpTransport>>,
+    /// Redacted URLs of the providers, empty when built from clients
+    urls: Vec<RedactedUrl>,
     /// Current active provider index
     current_index: Arc<RwLock<usize>>,
     /// Whether to rotate through providers on error or always start from the first
     sticky_failover: bool,
+    /// Whether to spread requests across providers in a round-robin fashion
+    load_balance: bool,
+    /// Next provider index to start from when load balancing
+    next_index: Arc<AtomicUsize>,
+    /// Per-provider weights biasing which provider is tried first
+    weights: Option<Vec<u32>>,
+}
+
+// Clients are skipped as their transport would print the raw URLs.
+impl std::fmt::Debug for FallbackProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FallbackProvider")
+            .field("provider_count", &self.providers.len())
+            .field("urls", &self.urls)
+            .field("sticky_failover", &self.sticky_failover)
+            .field("load_balance", &self.load_balance)
+            .field("weights", &self.weights)
+            .finish_non_exhaustive()
+    }
+}
+
+/// RPC URL that only prints its scheme, host and port, as the path or the
+/// query often contain an API key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RedactedUrl(Url);
+
+impl RedactedUrl {
+    pub fn new(url: Url) -> Self {
+        Self(url)
+    }
+
+    /// Returns the full URL, secrets included.
+    pub fn expose(&self) -> &Url {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RedactedUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}://{}",
+            self.0.scheme(),
+            self.0.host_str().unwrap_or_default()
+        )?;
+        if let Some(port) = self.0.port() {
+            write!(f, ":{port}")?;
+        }
+        let has_secrets = self.0.path() != "/"
+            || self.0.query().is_some()
+            || self.0.fragment().is_some()
+            || !self.0.username().is_empty()
+            || self.0.password().is_some();
+        if has_secrets {
+            f.write_str("/***")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for RedactedUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RedactedUrl({self})")
+    }
 }
 
 impl FallbackProvider {
     /// Creates a new fallback provider from a list of RPC URLs.
     ///
     /// The URLs are used in order of priority (first URL = highest priority).
+    /// Returns an error if `urls` is empty.
     pub fn new(urls: Vec<impl Into<Url>>) -> Result<Self, ProviderError> {
+        let urls: Vec<Url> = urls.into_iter().map(Into::into).collect();
+        if urls.is_empty() {
+            return Err(ProviderError::StarknetError(
+                starknet_rust::core::types::StarknetError::UnexpectedError(
+                    "FallbackProvider requires at least one url".to_string(),
+                ),
+            ));
+        }
         let providers = urls
-            .into_iter()
-            .map(|url| JsonRpcClient::new(HttpTransport::new(url.into())))
+            .iter()
+            .map(|url| JsonRpcClient::new(HttpTransport::new(url.clone())))
             .collect();
 
         Ok(Self {
             providers,
+            urls: urls.into_iter().map(RedactedUrl).collect(),
             current_index: Arc::new(RwLock::new(0)),
             sticky_failover: false,
+            load_balance: false,
+            next_index: Arc::new(AtomicUsize::new(0)),
+            weights: None,
         })
     }
 
@@ -95,11 +178,28 @@ impl FallbackProvider {
 
         Self {
             providers: clients,
+            urls: Vec::new(),
             current_index: Arc::new(RwLock::new(0)),
             sticky_failover: false,
+            load_balance: false,
+            next_index: Arc::new(AtomicUsize::new(0)),
+            weights: None,
         }
     }
 
+    /// Creates a new fallback provider from JSON-RPC clients and their weights.
+    ///
+    /// Requests start from a provider picked proportionally to its weight, e.g with
+    /// weights `[9, 1]` the first provider is tried first 9 times out of 10. Selection
+    /// is a deterministic weighted rotation, and failed requests still fall through
+    /// to every other provider.
+    pub fn from_weighted_clients(clients: Vec<(JsonRpcClient<HttpTransport>, u32)>) -> Self {
+        let (clients, weights): (Vec<_>, Vec<_>) = clients.into_iter().unzip();
+        let mut provider = Self::from_clients(clients);
+        provider.weights = Some(weights);
+        provider
+    }
+
     /// Enables sticky failover mode.
     ///
     /// When enabled, the provider will stick to a working provider instead of
@@ -110,6 +210,17 @@ impl FallbackProvider {
         self
     }
 
+    /// Enables load balancing mode.
+    ///
+    /// When enabled, each request starts from the next provider in a round-robin
+    /// fashion instead of the primary, still falling through to the other
+    /// providers on error. Takes precedence over sticky failover, which is then
+    /// ignored.
+    pub fn with_load_balance(mut self, load_balance: bool) -> Self {
+        self.load_balance = load_balance;
+        self
+    }
+
     /// Gets the number of available providers.
     pub fn provider_count(&self) -> usize {
         self.providers.len()
@@ -167,18 +278,10 @@ impl FallbackProvider {
                             }
                         }
                     }
-                    Err(e) => match e {
-                        ProviderError::RateLimited => {
-                            sleep(check_interval).await;
-                        }
-                        _ if e
-                            .to_string()
-                            .contains("Unable to complete request at this time.") =>
-                        {
-                            sleep(check_interval).await;
-                        }
-                        _ => return Err(e),
-                    },
+                    Err(e) if Self::is_transient(&e) => {
+                        sleep(check_interval).await;
+                    }
+                    Err(e) => return Err(e),
                 }
             }
         };
@@ -192,6 +295,89 @@ impl FallbackProvider {
         })?
     }
 
+    /// Same as [`FallbackProvider::wait_for`], but returns early with
+    /// [`WaitForError::Cancelled`] as soon as `cancel` is cancelled.
+    #[cfg(feature = "services")]
+    pub async fn wait_for_with_cancel<H>(
+        &self,
+        transaction_hash: H,
+        target: WaitForTarget,
+        check_interval: Option<Duration>,
+        timeout_duration: Option<Duration>,
+        cancel: &crate::services::ServiceContext,
+    ) -> Result<(), WaitForError>
+    where
+        H: AsRef<Felt> + Send + Sync + Copy,
+    {
+        tokio::select! {
+            biased;
+            () = cancel.cancelled() => Err(WaitForError::Cancelled),
+            res = self.wait_for(transaction_hash, target, check_interval, timeout_duration) => {
+                res.map_err(WaitForError::Provider)
+            }
+        }
+    }
+
+    /// Whether sticky failover drives the provider selection, i.e it is enabled and
+    /// neither load balancing nor weights take precedence over it.
+    fn is_sticky(&self) -> bool {
+        self.sticky_failover && !self.load_balance && self.weights.is_none()
+    }
+
+    /// Index of the provider the next request should be sent to first.
+    async fn start_index(&self) -> usize {
+        if let Some(weights) = &self.weights {
+            self.weighted_start_index(weights)
+        } else if self.load_balance {
+            self.next_index.fetch_add(1, Ordering::Relaxed) % self.providers.len()
+        } else if self.is_sticky() {
+            *self.current_index.read().await
+        } else {
+            0
+        }
+    }
+
+    /// Picks a provider index proportionally to `weights` with a deterministic rotation.
+    fn weighted_start_index(&self, weights: &[u32]) -> usize {
+        let total_weight: u64 = weights.iter().map(|weight| u64::from(*weight)).sum();
+        if total_weight == 0 {
+            return 0;
+        }
+
+        let mut position = self.next_index.fetch_add(1, Ordering::Relaxed) as u64 % total_weight;
+        for (index, weight) in weights.iter().enumerate() {
+            let weight = u64::from(*weight);
+            if position < weight {
+                return index;
+            }
+            position -= weight;
+        }
+        0
+    }
+
+    /// Whether `err` is worth retrying, either later or on another provider.
+    fn is_transient(err: &ProviderError) -> bool {
+        match err {
+            ProviderError::RateLimited => true,
+            ProviderError::Other(err) => {
+                let message = err.to_string();
+                message.contains("Unable to complete request at this time.")
+                    || message.contains("error sending request")
+            }
+            _ => false,
+        }
+    }
+
+    /// Short name of the kind of `err`, for logs.
+    const fn error_kind(err: &ProviderError) -> &'static str {
+        match err {
+            ProviderError::StarknetError(_) => "starknet_error",
+            ProviderError::RateLimited => "rate_limited",
+            ProviderError::ArrayLengthMismatch => "array_length_mismatch",
+            ProviderError::Other(_) => "other",
+        }
+    }
+
     async fn execute_with_fallback<T, F>(&self, mut f: F) -> Result<T, ProviderError>
     where
         for<'a> F: FnMut(
@@ -200,11 +386,7 @@ impl FallbackProvider {
             Box<dyn std::future::Future<Output = Result<T, ProviderError>> + Send + 'a>,
         >,
     {
-        let start_index = if self.sticky_failover {
-            *self.current_index.read().await
-        } else {
-            0
-        };
+        let start_index = self.start_index().await;
 
         let mut last_error = None;
 
@@ -215,40 +397,35 @@ impl FallbackProvider {
 
             match f(provider).await {
                 Ok(result) => {
-                    // Update current index on success if using sticky failover
-                    if self.sticky_failover && index != *self.current_index.read().await {
-                        *self.current_index.write().await = index;
+                    // Update current index on success if sticky failover drives the
+                    // selection, so the switch is only logged when it matters
+                    if self.is_sticky() {
+                        let previous_index = *self.current_index.read().await;
+                        if index != previous_index {
+                            *self.current_index.write().await = index;
+                            tracing::info!(
+                                previous_index,
+                                provider_index = index,
+                                "Sticky failover switched the active RPC provider"
+                            );
+                        }
                     }
                     return Ok(result);
                 }
-                Err(err) => {
-                    match err {
-                        // If we're rate limited, we try a new provider
-                        ProviderError::RateLimited => {
-                            last_error = Some(err);
-                            continue;
-                        }
-                        ProviderError::Other(err)
-                            if err
-                                .to_string()
-                                .contains("Unable to complete request at this time.") =>
-                        {
-                            last_error = Some(ProviderError::Other(err));
-                            continue;
-                        }
-                        ProviderError::Other(err)
-                            if err.to_string().contains("error sending request") =>
-                        {
-                            last_error = Some(ProviderError::Other(err));
-                            continue;
-                        }
-                        // Else we just bubble up the error
-                        err => {
-                            return Err(err);
-                        }
-                    }
-                    // Continue to next provider
+                // Transient errors make us try the next provider
+                Err(err) if Self::is_transient(&err) => {
+                    // The error itself is not logged: reqwest appends the full URL,
+                    // API key included, to its network errors
+                    tracing::warn!(
+                        provider_index = index,
+                        endpoint = self.urls.get(index).map(tracing::field::display),
+                        error_kind = Self::error_kind(&err),
+                        "RPC provider failed, falling back to the next one"
+                    );
+                    last_error = Some(err);
                 }
+                // Else we just bubble up the error
+                Err(err) => return Err(err),
             }
         }
 
@@ -716,3 +893,137 @@ impl Provider for FallbackProvider {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(i: usize) -> Url {
+        Url::parse(&format!("http://provider-{i}.example.com")).unwrap()
+    }
+
+    fn provider(count: usize) -> FallbackProvider {
+        FallbackProvider::new((0..count).map(url).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_redacted_url() {
+        let url = Url::parse("https://rpc.example.com/v0_8/key?apikey=secret").unwrap();
+        let redacted = RedactedUrl::new(url.clone());
+        assert_eq!(redacted.to_string(), "https://rpc.example.com/***");
+        assert!(!format!("{redacted:?}").contains("secret"));
+        assert_eq!(redacted.expose(), &url);
+
+        let plain = RedactedUrl::new(Url::parse("http://localhost:5050").unwrap());
+        assert_eq!(plain.to_string(), "http://localhost:5050");
+
+        let provider = FallbackProvider::new(vec![url]).unwrap();
+        assert!(!format!("{provider:?}").contains("secret"));
+    }
+
+    #[test]
+    fn test_new_rejects_empty_urls() {
+        let err = FallbackProvider::new(Vec::<Url>::new()).unwrap_err();
+        assert!(err.to_string().contains("at least one url"));
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("{0}")]
+    struct OtherError(&'static str);
+
+    impl starknet_rust::providers::ProviderImplError for OtherError {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_is_transient() {
+        let other = |message| ProviderError::Other(Box::new(OtherError(message)));
+
+        assert!(FallbackProvider::is_transient(&ProviderError::RateLimited));
+        assert!(FallbackProvider::is_transient(&other(
+            "Unable to complete request at this time."
+        )));
+        assert!(FallbackProvider::is_transient(&other(
+            "error sending request for url"
+        )));
+        assert!(!FallbackProvider::is_transient(&other("invalid params")));
+        assert!(!FallbackProvider::is_transient(
+            &ProviderError::ArrayLengthMismatch
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_start_index_defaults_to_primary() {
+        let provider = provider(3);
+        for _ in 0..3 {
+            assert_eq!(provider.start_index().await, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_balance_rotates_providers() {
+        let provider = provider(3).with_load_balance(true);
+
+        let mut indexes = Vec::new();
+        for _ in 0..6 {
+            indexes.push(provider.start_index().await);
+        }
+        assert_eq!(indexes, vec![0, 1, 2, 0, 1, 2]);
+
+        // Clones share the rotation
+        let clone = provider.clone();
+        assert_eq!(clone.start_index().await, 0);
+        assert_eq!(provider.start_index().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_balance_overrides_sticky_failover() {
+        let provider = provider(3)
+            .with_sticky_failover(true)
+            .with_load_balance(true);
+
+        let mut indexes = Vec::new();
+        for _ in 0..3 {
+            let index = provider
+                .execute_with_fallback(|client| {
+                    let index = provider
+                        .providers
+                        .iter()
+                        .position(|p| std::ptr::eq(p, client))
+                        .unwrap();
+                    Box::pin(async move { Ok(index) })
+                })
+                .await
+                .unwrap();
+            indexes.push(index);
+        }
+
+        assert_eq!(indexes, vec![0, 1, 2]);
+        assert_eq!(provider.current_provider_index().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_selection() {
+        let provider = FallbackProvider::from_weighted_clients(vec![
+            (JsonRpcClient::new(HttpTransport::new(url(0))), 9),
+            (JsonRpcClient::new(HttpTransport::new(url(1))), 1),
+        ]);
+
+        let mut hits = [0; 2];
+        for _ in 0..100 {
+            hits[provider.start_index().await] += 1;
+        }
+        assert_eq!(hits, [90, 10]);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_selection_all_zero() {
+        let provider = FallbackProvider::from_weighted_clients(vec![
+            (JsonRpcClient::new(HttpTransport::new(url(0))), 0),
+            (JsonRpcClient::new(HttpTransport::new(url(1))), 0),
+        ]);
+        assert_eq!(provider.start_index().await, 0);
+    }
+}
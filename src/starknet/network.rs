@@ -1,3 +1,5 @@
+use starknet_rust::{core::types::Felt, providers::Url};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize,))]
 #[cfg_attr(
@@ -12,6 +14,36 @@ pub enum StarknetNetwork {
     Sepolia,
 }
 
+impl StarknetNetwork {
+    /// Returns the chain id of the network, i.e `SN_MAIN` or `SN_SEPOLIA` as a short string.
+    pub fn chain_id_felt(&self) -> Felt {
+        match self {
+            Self::Mainnet => Felt::from_hex_unchecked("0x534e5f4d41494e"),
+            Self::Sepolia => Felt::from_hex_unchecked("0x534e5f5345504f4c4941"),
+        }
+    }
+
+    /// Returns public RPC endpoints for the network, sorted by priority.
+    ///
+    /// Meant to bootstrap a `FallbackProvider` when no private RPC is configured.
+    pub fn default_rpc_urls(&self) -> Vec<Url> {
+        let urls: &[&str] = match self {
+            Self::Mainnet => &[
+                "https://api.cartridge.gg/x/starknet/mainnet",
+                "https://starknet-mainnet.public.blastapi.io",
+            ],
+            Self::Sepolia => &[
+                "https://api.cartridge.gg/x/starknet/sepolia",
+                "https://starknet-sepolia.public.blastapi.io",
+            ],
+        };
+
+        urls.iter()
+            .map(|url| Url::parse(url).expect("Invalid default RPC url"))
+            .collect()
+    }
+}
+
 impl std::fmt::Display for StarknetNetwork {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -26,3 +58,31 @@ impl Default for StarknetNetwork {
         Self::Mainnet
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use starknet_rust::core::utils::cairo_short_string_to_felt;
+
+    use super::*;
+
+    #[test]
+    fn test_chain_id_felt() {
+        assert_eq!(
+            StarknetNetwork::Mainnet.chain_id_felt(),
+            cairo_short_string_to_felt("SN_MAIN").unwrap()
+        );
+        assert_eq!(
+            StarknetNetwork::Sepolia.chain_id_felt(),
+            cairo_short_string_to_felt("SN_SEPOLIA").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_default_rpc_urls() {
+        for network in [StarknetNetwork::Mainnet, StarknetNetwork::Sepolia] {
+            let urls = network.default_rpc_urls();
+            assert!(!urls.is_empty());
+            assert!(urls.iter().all(|url| url.scheme() == "https"));
+        }
+    }
+}
@@ -391,6 +391,8 @@ impl PrimitiveType {
 pub struct Domain {
     pub name: String,
     pub version: String,
+    /// Either a hex (`"0x534e5f5345504f4c4941"`), a decimal (`"1"`) or a
+    /// short-string (`"SN_SEPOLIA"`) felt. Numeric forms take precedence.
     #[serde(rename = "chainId")]
     pub chain_id: String,
     pub revision: Option<String>,
@@ -467,6 +469,17 @@ impl TypedData {
         }
     }
 
+    /// Serializes the typed data to a JSON string, keeping the declared order of
+    /// `types` & `message` fields which the hash depends on.
+    ///
+    /// The data is serialized directly rather than through a `serde_json::Value`,
+    /// whose `Map` sorts keys unless serde_json's `preserve_order` is enabled.
+    pub fn to_json_string(&self) -> Result<String, SignerError> {
+        serde_json::to_string(self).map_err(|e| {
+            SignerError::InvalidMessage(format!("Failed to serialize typed data: {e}"))
+        })
+    }
+
     pub fn encode(&self, account: Felt) -> Result<TypedDataHash, SignerError> {
         let preset_types = get_preset_types();
 
@@ -587,6 +600,31 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("0x534e5f5345504f4c4941")]
+    #[case("393402133025997798000961")]
+    #[case("SN_SEPOLIA")]
+    fn test_domain_chain_id_forms(#[case] chain_id: &str) {
+        let reader = std::io::BufReader::new(EXAMPLE_BASE_TYPES.as_bytes());
+        let typed_data: TypedData = serde_json::from_reader(reader).unwrap();
+
+        let expected = cairo_short_string_to_felt("SN_SEPOLIA").unwrap();
+        assert_eq!(get_hex(chain_id).unwrap(), expected);
+
+        let reference = Domain::new("StarkNet Mail", "1", "0x534e5f5345504f4c4941", Some("1"));
+        let domain = Domain::new("StarkNet Mail", "1", chain_id, Some("1"));
+        assert_eq!(
+            domain.encode(&typed_data.types).unwrap(),
+            reference.encode(&typed_data.types).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_domain_decimal_chain_id_is_numeric() {
+        assert_eq!(get_hex("1").unwrap(), Felt::ONE);
+        assert_eq!(get_hex("0x1").unwrap(), Felt::ONE);
+    }
+
     #[rstest]
     #[case(
         EXAMPLE_BASE_TYPES,
@@ -624,6 +662,25 @@ mod tests {
         assert_eq!(message_hash, Felt::from_str(expected_hash_hex).unwrap());
     }
 
+    #[rstest]
+    #[case(EXAMPLE_BASE_TYPES)]
+    #[case(EXAMPLE_ENUM)]
+    #[case(MAIL_STRUCT_ARRAY)]
+    fn test_to_json_string_keeps_hash(#[case] example_data: &str) {
+        let address = Felt::from_str("0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826").unwrap();
+        let typed_data: TypedData = serde_json::from_str(example_data).unwrap();
+
+        let json = typed_data.to_json_string().unwrap();
+        let roundtrip: TypedData = serde_json::from_str(&json).unwrap();
+
+        assert!(typed_data.types.keys().eq(roundtrip.types.keys()));
+        assert!(typed_data.message.keys().eq(roundtrip.message.keys()));
+        assert_eq!(
+            roundtrip.encode(address).unwrap().hash,
+            typed_data.encode(address).unwrap().hash
+        );
+    }
+
     const EXAMPLE_BASE_TYPES: &str = r#"
 {
   "types": {
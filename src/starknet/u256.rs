@@ -5,7 +5,7 @@ use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use starknet_rust::core::types::Felt;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct StarknetU256 {
     pub low: Felt,
     pub high: Felt,
@@ -16,12 +16,31 @@ impl StarknetU256 {
         low: Felt::ZERO,
         high: Felt::ZERO,
     };
+
+    /// Returns true if both limbs are zero
+    pub fn is_zero(&self) -> bool {
+        self.low == Felt::ZERO && self.high == Felt::ZERO
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 #[error("Slice too long, max is 32, received {0}")]
 pub struct StarknetU256FromBytesSliceError(usize);
 
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum U256ConversionError {
+    #[error("Expected 2 felts for a u256, received {0}")]
+    InvalidLength(usize),
+    #[error("u256 limb does not fit in 128 bits: {0:#x}")]
+    LimbTooBig(Felt),
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum U256DivError {
+    #[error("Division by zero")]
+    DivisionByZero,
+}
+
 impl StarknetU256 {
     pub fn from_parts<L: Into<u128>, H: Into<u128>>(low: L, high: H) -> Self {
         let low: u128 = low.into();
@@ -40,6 +59,67 @@ impl StarknetU256 {
         ret
     }
 
+    /// Returns the minimal number of bits needed to represent the value
+    pub fn bit_len(&self) -> u32 {
+        let bytes = self.to_bytes_be();
+        match bytes.iter().position(|b| *b != 0) {
+            Some(first) => (32 - first as u32) * 8 - bytes[first].leading_zeros(),
+            None => 0,
+        }
+    }
+
+    /// Returns the number of significant bytes of the big-endian representation
+    pub fn byte_len(&self) -> usize {
+        self.to_bytes_be().iter().skip_while(|b| **b == 0).count()
+    }
+
+    /// Returns the limbs in Cairo's u256 calldata order, i.e `[low, high]`
+    pub fn to_calldata(&self) -> [Felt; 2] {
+        [self.low, self.high]
+    }
+
+    /// Reads a u256 from calldata in Cairo's order, i.e `[low, high]`
+    pub fn from_calldata(calldata: &[Felt]) -> Result<Self, U256ConversionError> {
+        let [low, high] = calldata else {
+            return Err(U256ConversionError::InvalidLength(calldata.len()));
+        };
+
+        for limb in [low, high] {
+            if limb.to_bytes_be()[..16].iter().any(|byte| *byte != 0) {
+                return Err(U256ConversionError::LimbTooBig(*limb));
+            }
+        }
+
+        Ok(Self {
+            low: *low,
+            high: *high,
+        })
+    }
+
+    /// Returns the value as a `u128`, `None` if it doesn't fit, i.e the high limb isn't zero
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.high != Felt::ZERO {
+            return None;
+        }
+        let bytes = self.low.to_bytes_be();
+        if bytes[..16].iter().any(|byte| *byte != 0) {
+            return None;
+        }
+        Some(u128::from_be_bytes(bytes[16..].try_into().unwrap()))
+    }
+
+    /// Returns the quotient & remainder of the division of `self` by `rhs`
+    pub fn div_rem(&self, rhs: &Self) -> Result<(Self, Self), U256DivError> {
+        if rhs.is_zero() {
+            return Err(U256DivError::DivisionByZero);
+        }
+        let (lhs, rhs) = (BigUint::from(self), BigUint::from(rhs));
+        // Both are at most `self`, so they fit in a u256
+        let quotient = Self::try_from(&lhs / &rhs).expect("quotient fits in a u256");
+        let remainder = Self::try_from(lhs % rhs).expect("remainder fits in a u256");
+        Ok((quotient, remainder))
+    }
+
     pub fn from_bytes(bytes: &[u8; 32]) -> Self {
         Self {
             low: Felt::from(u128::from_be_bytes(bytes[16..].try_into().unwrap())),
@@ -86,6 +166,12 @@ impl core::fmt::Display for StarknetU256 {
     }
 }
 
+impl From<u128> for StarknetU256 {
+    fn from(value: u128) -> Self {
+        Self::from_parts(value, 0u128)
+    }
+}
+
 impl From<StarknetU256> for BigUint {
     fn from(value: StarknetU256) -> Self {
         let bytes = value.to_bytes_be();
@@ -142,10 +228,12 @@ impl TryFrom<BigUint> for StarknetU256 {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use num_bigint::BigUint;
     use starknet_rust::core::types::Felt;
 
-    use super::{StarknetU256, StarknetU256FromBytesSliceError};
+    use super::{StarknetU256, StarknetU256FromBytesSliceError, U256ConversionError, U256DivError};
 
     #[test]
     fn test_zero() {
@@ -154,6 +242,67 @@ mod tests {
         assert_eq!(zero.high, Felt::ZERO);
     }
 
+    #[test]
+    fn test_is_zero() {
+        assert!(StarknetU256::ZERO.is_zero());
+        assert!(StarknetU256::from_parts(0u64, 0u64).is_zero());
+        assert!(!StarknetU256::from_parts(1u64, 0u64).is_zero());
+        assert!(!StarknetU256::from_parts(0u64, 1u64).is_zero());
+    }
+
+    #[test]
+    fn test_calldata_roundtrip() {
+        let value = StarknetU256::from_parts(u128::MAX, 42u64);
+        let calldata = value.to_calldata();
+        assert_eq!(calldata, [Felt::from(u128::MAX), Felt::from(42u64)]);
+        assert_eq!(StarknetU256::from_calldata(&calldata).unwrap(), value);
+    }
+
+    #[test]
+    fn test_u128_conversions() {
+        let value = StarknetU256::from(1_000_000u128);
+        assert_eq!(value, StarknetU256::from_parts(1_000_000u128, 0u64));
+        assert_eq!(value.to_u128(), Some(1_000_000));
+
+        let max = StarknetU256::from(u128::MAX);
+        assert_eq!(max.to_u128(), Some(u128::MAX));
+
+        assert_eq!(StarknetU256::from_parts(0u64, 1u64).to_u128(), None);
+    }
+
+    #[test]
+    fn test_from_calldata_errors() {
+        assert_eq!(
+            StarknetU256::from_calldata(&[Felt::ONE, Felt::ZERO, Felt::ZERO]),
+            Err(U256ConversionError::InvalidLength(3))
+        );
+        assert_eq!(
+            StarknetU256::from_calldata(&[Felt::ONE]),
+            Err(U256ConversionError::InvalidLength(1))
+        );
+
+        let too_big = Felt::from(u128::MAX) + Felt::ONE;
+        assert_eq!(
+            StarknetU256::from_calldata(&[Felt::ZERO, too_big]),
+            Err(U256ConversionError::LimbTooBig(too_big))
+        );
+    }
+
+    #[test]
+    fn test_hash_dedup() {
+        let values: HashSet<StarknetU256> = [
+            StarknetU256::ZERO,
+            StarknetU256::from_parts(0u64, 0u64),
+            StarknetU256::from_parts(42u64, 0u64),
+            StarknetU256::from_parts(42u64, 0u64),
+            StarknetU256::from_parts(0u64, 42u64),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(values.len(), 3);
+    }
+
     #[test]
     fn test_from_parts() {
         let value = StarknetU256::from_parts(42u64, 0u64);
@@ -181,6 +330,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bit_and_byte_len() {
+        assert_eq!(StarknetU256::ZERO.bit_len(), 0);
+        assert_eq!(StarknetU256::ZERO.byte_len(), 0);
+
+        let value = StarknetU256::from_parts(1u64, 0u64);
+        assert_eq!(value.bit_len(), 1);
+        assert_eq!(value.byte_len(), 1);
+
+        let value = StarknetU256::from_parts(0xFFu64, 0u64);
+        assert_eq!(value.bit_len(), 8);
+        assert_eq!(value.byte_len(), 1);
+
+        let value = StarknetU256::from_parts(u128::MAX, 0u64);
+        assert_eq!(value.bit_len(), 128);
+        assert_eq!(value.byte_len(), 16);
+
+        let value = StarknetU256::from_parts(0u64, 1u64);
+        assert_eq!(value.bit_len(), 129);
+        assert_eq!(value.byte_len(), 17);
+
+        let value = StarknetU256::from_parts(u128::MAX, u128::MAX);
+        assert_eq!(value.bit_len(), 256);
+        assert_eq!(value.byte_len(), 32);
+    }
+
     #[test]
     fn test_from_bytes() {
         let mut bytes = [0u8; 32];
@@ -262,6 +437,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_div_rem() {
+        let value = StarknetU256::from_parts(0u64, 2u64);
+        assert_eq!(
+            value.div_rem(&StarknetU256::from(2u128)).unwrap(),
+            (StarknetU256::from_parts(0u64, 1u64), StarknetU256::ZERO)
+        );
+
+        assert_eq!(
+            StarknetU256::from(1_234_567u128)
+                .div_rem(&StarknetU256::from(1_000u128))
+                .unwrap(),
+            (StarknetU256::from(1_234u128), StarknetU256::from(567u128))
+        );
+
+        assert_eq!(
+            value.div_rem(&StarknetU256::ZERO),
+            Err(U256DivError::DivisionByZero)
+        );
+    }
+
     #[test]
     fn test_display() {
         let value = StarknetU256 {
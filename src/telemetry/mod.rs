@@ -19,6 +19,33 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
+#[doc(hidden)]
+pub use tracing as __tracing;
+
+/// Opens an `info` span named `service` for a service loop, with the standard
+/// `service.name` & optional `iteration` fields.
+///
+/// Spans are no-ops when no subscriber records them, i.e when telemetry isn't initialized.
+///
+/// ```
+/// let span = pragma_common::telemetry::instrument_service!("price_fetcher", 3);
+/// let _enter = span.enter();
+/// ```
+#[macro_export]
+macro_rules! instrument_service {
+    ($name:expr) => {
+        $crate::telemetry::__tracing::info_span!("service", service.name = $name)
+    };
+    ($name:expr, $iteration:expr) => {
+        $crate::telemetry::__tracing::info_span!(
+            "service",
+            service.name = $name,
+            iteration = $iteration
+        )
+    };
+}
+pub use crate::instrument_service;
+
 #[derive(thiserror::Error, Debug)]
 pub enum TelemetryError {
     #[error("trace error: {0}")]
@@ -188,3 +215,27 @@ pub fn init_meter_provider(
 
     Ok(metrics_provider)
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_instrument_service_span() {
+        let span = instrument_service!("price_fetcher", 3);
+        assert!(span.is_disabled());
+
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry());
+        let span = instrument_service!("price_fetcher", 3);
+        let metadata = span.metadata().unwrap();
+        assert_eq!(metadata.name(), "service");
+        assert!(metadata.fields().field("service.name").is_some());
+        assert!(metadata.fields().field("iteration").is_some());
+
+        let span = instrument_service!("price_fetcher");
+        assert!(span
+            .metadata()
+            .unwrap()
+            .fields()
+            .field("iteration")
+            .is_none());
+    }
+}
@@ -20,4 +20,52 @@ impl Side {
             Side::Short => Side::Long,
         }
     }
+
+    /// Returns the signed exposure of `magnitude`, positive for longs and negative for shorts
+    pub fn apply(&self, magnitude: f64) -> f64 {
+        match self {
+            Side::Long => magnitude,
+            Side::Short => -magnitude,
+        }
+    }
+
+    /// Returns the side of a signed exposure, `None` if it is zero or NaN
+    pub fn from_signed(value: f64) -> Option<Self> {
+        if value > 0.0 {
+            Some(Side::Long)
+        } else if value < 0.0 {
+            Some(Side::Short)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::ops::Neg for Side {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.opposite()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::rstest]
+    #[case(Side::Long, 2.5, 2.5)]
+    #[case(Side::Short, 2.5, -2.5)]
+    fn test_apply_and_from_signed(#[case] side: Side, #[case] magnitude: f64, #[case] signed: f64) {
+        assert_eq!(side.apply(magnitude), signed);
+        assert_eq!(Side::from_signed(signed), Some(side));
+        assert_eq!(-side, side.opposite());
+    }
+
+    #[test]
+    fn test_from_signed_zero() {
+        assert_eq!(Side::from_signed(0.0), None);
+        assert_eq!(Side::from_signed(-0.0), None);
+        assert_eq!(Side::from_signed(f64::NAN), None);
+    }
 }
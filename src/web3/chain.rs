@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use super::{Token, APT, AVAX, BNB, ETH, POL, SOL, SUI, USDC, USDT, WLD, XDAI};
+use super::{Token, APT, AVAX, BNB, ETH, KNOWN_TOKENS, POL, SOL, SUI, USDC, USDT, WLD, XDAI};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ChainError {
@@ -8,7 +8,29 @@ pub enum ChainError {
     UnknownChain(String),
 }
 
-#[derive(Debug, Copy, Hash, Eq, Clone, PartialEq, PartialOrd, Ord)]
+/// Layer of a [`Chain`], see [`Chain::layer`]
+#[derive(Debug, Copy, Hash, Eq, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum ChainLayer {
+    /// Chain with its own consensus & settlement
+    L1,
+    /// Rollup settling on Ethereum
+    L2,
+    /// Application specific chain
+    AppChain,
+}
+
+/// Virtual machine of a [`Chain`], which drives its address format & signing scheme
+#[derive(Debug, Copy, Hash, Eq, Clone, PartialEq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -19,6 +41,31 @@ pub enum ChainError {
     derive(borsh::BorshSerialize, borsh::BorshDeserialize)
 )]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum VmFamily {
+    Evm,
+    /// Solana virtual machine
+    Svm,
+    /// Sui & Aptos
+    Move,
+    /// Starknet
+    Cairo,
+}
+
+/// A blockchain supported by Pragma
+///
+/// Serialized in lowercase, e.g `"ethereum"`. Deserialization is case-insensitive:
+/// `"ethereum"`, `"Ethereum"` and `"ETHEREUM"` are all accepted.
+#[derive(Debug, Copy, Hash, Eq, Clone, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub enum Chain {
     Starknet,
     Solana,
@@ -87,6 +134,66 @@ impl Chain {
         )
     }
 
+    /// Returns the virtual machine family of the chain
+    pub const fn vm_family(&self) -> VmFamily {
+        match self {
+            Self::Starknet => VmFamily::Cairo,
+            Self::Solana => VmFamily::Svm,
+            Self::Sui | Self::Aptos => VmFamily::Move,
+            Self::Ethereum
+            | Self::Base
+            | Self::Arbitrum
+            | Self::Optimism
+            | Self::ZkSync
+            | Self::Polygon
+            | Self::Bnb
+            | Self::Avalanche
+            | Self::Gnosis
+            | Self::Worldchain => VmFamily::Evm,
+        }
+    }
+
+    pub const fn is_svm(&self) -> bool {
+        matches!(self.vm_family(), VmFamily::Svm)
+    }
+
+    pub const fn is_move(&self) -> bool {
+        matches!(self.vm_family(), VmFamily::Move)
+    }
+
+    pub const fn is_cairo(&self) -> bool {
+        matches!(self.vm_family(), VmFamily::Cairo)
+    }
+
+    /// Returns the layer of the chain.
+    ///
+    /// Starknet & ZkSync are validity rollups and Base, Optimism, Arbitrum &
+    /// Worldchain optimistic rollups, all settling on Ethereum. Sidechains with
+    /// their own validator set, e.g Polygon PoS or Gnosis, are considered L1s.
+    pub const fn layer(&self) -> ChainLayer {
+        match self {
+            Self::Starknet
+            | Self::Base
+            | Self::Arbitrum
+            | Self::Optimism
+            | Self::ZkSync
+            | Self::Worldchain => ChainLayer::L2,
+            Self::Ethereum
+            | Self::Solana
+            | Self::Sui
+            | Self::Aptos
+            | Self::Polygon
+            | Self::Bnb
+            | Self::Avalanche
+            | Self::Gnosis => ChainLayer::L1,
+        }
+    }
+
+    /// Returns true if the chain is a rollup, i.e a [`ChainLayer::L2`]
+    pub const fn is_rollup(&self) -> bool {
+        matches!(self.layer(), ChainLayer::L2)
+    }
+
     #[must_use]
     /// Returns the gas token for the chain
     pub fn gas_token(&self) -> Token {
@@ -123,6 +230,99 @@ impl Chain {
             | Self::Sui => USDC(),
         }
     }
+
+    /// Returns every known stablecoin, as per [`Token::is_stablecoin`], with an
+    /// address on the chain
+    pub fn stablecoins(&self) -> Vec<Token> {
+        KNOWN_TOKENS
+            .iter()
+            .map(|token| token())
+            .filter(|token| token.is_stablecoin() && token.address(*self).is_some())
+            .collect()
+    }
+}
+
+/// A [`Chain`] on either its mainnet or its testnet
+///
+/// `Chain` only models mainnets, testnets are represented by wrapping the
+/// mainnet chain with `testnet: true`. The EVM testnets used are the current
+/// public ones, e.g Sepolia for Ethereum or Amoy for Polygon.
+#[derive(Debug, Copy, Hash, Eq, Clone, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ChainNetwork {
+    pub chain: Chain,
+    pub testnet: bool,
+}
+
+impl ChainNetwork {
+    pub const fn mainnet(chain: Chain) -> Self {
+        Self {
+            chain,
+            testnet: false,
+        }
+    }
+
+    pub const fn testnet(chain: Chain) -> Self {
+        Self {
+            chain,
+            testnet: true,
+        }
+    }
+
+    pub const fn is_testnet(&self) -> bool {
+        self.testnet
+    }
+
+    pub fn from_chain_id(id: u64) -> Option<Self> {
+        if let Some(chain) = Chain::from_chain_id(id) {
+            return Some(Self::mainnet(chain));
+        }
+        let chain = match id {
+            11_155_111 => Chain::Ethereum,
+            11_155_420 => Chain::Optimism,
+            80_002 => Chain::Polygon,
+            300 => Chain::ZkSync,
+            84_532 => Chain::Base,
+            421_614 => Chain::Arbitrum,
+            97 => Chain::Bnb,
+            43_113 => Chain::Avalanche,
+            10_200 => Chain::Gnosis,
+            4_801 => Chain::Worldchain,
+            _ => return None,
+        };
+        Some(Self::testnet(chain))
+    }
+
+    #[must_use]
+    pub const fn chain_id(&self) -> Option<u64> {
+        if !self.testnet {
+            return self.chain.chain_id();
+        }
+        match self.chain {
+            Chain::Ethereum => Some(11_155_111),
+            Chain::Optimism => Some(11_155_420),
+            Chain::Polygon => Some(80_002),
+            Chain::ZkSync => Some(300),
+            Chain::Base => Some(84_532),
+            Chain::Arbitrum => Some(421_614),
+            Chain::Bnb => Some(97),
+            Chain::Avalanche => Some(43_113),
+            Chain::Gnosis => Some(10_200),
+            Chain::Worldchain => Some(4_801),
+            _ => None,
+        }
+    }
+}
+
+impl From<Chain> for ChainNetwork {
+    fn from(chain: Chain) -> Self {
+        Self::mainnet(chain)
+    }
 }
 
 impl std::fmt::Display for Chain {
@@ -154,3 +354,204 @@ impl FromStr for Chain {
         }
     }
 }
+
+#[cfg(feature = "proto")]
+impl Chain {
+    pub(crate) const fn to_proto_i32(self) -> i32 {
+        match self {
+            Self::Starknet => crate::schema::Chain::Starknet as i32,
+            Self::Solana => crate::schema::Chain::Solana as i32,
+            Self::Sui => crate::schema::Chain::Sui as i32,
+            Self::Aptos => crate::schema::Chain::Aptos as i32,
+            Self::Ethereum => crate::schema::Chain::Ethereum as i32,
+            Self::Base => crate::schema::Chain::Base as i32,
+            Self::Arbitrum => crate::schema::Chain::Arbitrum as i32,
+            Self::Optimism => crate::schema::Chain::Optimism as i32,
+            Self::ZkSync => crate::schema::Chain::Zksync as i32,
+            Self::Polygon => crate::schema::Chain::Polygon as i32,
+            Self::Bnb => crate::schema::Chain::Bnb as i32,
+            Self::Avalanche => crate::schema::Chain::Avalanche as i32,
+            Self::Gnosis => crate::schema::Chain::Gnosis as i32,
+            Self::Worldchain => crate::schema::Chain::Worldchain as i32,
+        }
+    }
+
+    pub(crate) fn from_proto_i32(value: i32) -> Result<Self, prost::DecodeError> {
+        match value {
+            x if x == crate::schema::Chain::Starknet as i32 => Ok(Self::Starknet),
+            x if x == crate::schema::Chain::Solana as i32 => Ok(Self::Solana),
+            x if x == crate::schema::Chain::Sui as i32 => Ok(Self::Sui),
+            x if x == crate::schema::Chain::Aptos as i32 => Ok(Self::Aptos),
+            x if x == crate::schema::Chain::Ethereum as i32 => Ok(Self::Ethereum),
+            x if x == crate::schema::Chain::Base as i32 => Ok(Self::Base),
+            x if x == crate::schema::Chain::Arbitrum as i32 => Ok(Self::Arbitrum),
+            x if x == crate::schema::Chain::Optimism as i32 => Ok(Self::Optimism),
+            x if x == crate::schema::Chain::Zksync as i32 => Ok(Self::ZkSync),
+            x if x == crate::schema::Chain::Polygon as i32 => Ok(Self::Polygon),
+            x if x == crate::schema::Chain::Bnb as i32 => Ok(Self::Bnb),
+            x if x == crate::schema::Chain::Avalanche as i32 => Ok(Self::Avalanche),
+            x if x == crate::schema::Chain::Gnosis as i32 => Ok(Self::Gnosis),
+            x if x == crate::schema::Chain::Worldchain as i32 => Ok(Self::Worldchain),
+            _ => Err(prost::DecodeError::new(format!(
+                "Unknown chain value: {value}",
+            ))),
+        }
+    }
+}
+
+/// Accepts either a case-insensitive chain name or an EVM chain id, e.g `"ethereum"` or `1`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Chain {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum ChainRepr {
+            Name(String),
+            Id(u64),
+        }
+
+        match ChainRepr::deserialize(deserializer)? {
+            ChainRepr::Name(name) => name.parse().map_err(serde::de::Error::custom),
+            ChainRepr::Id(id) => Self::from_chain_id(id)
+                .ok_or_else(|| serde::de::Error::custom(format!("Unknown chain id: {id}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::rstest]
+    #[case(11_155_111, ChainNetwork::testnet(Chain::Ethereum))]
+    #[case(84_532, ChainNetwork::testnet(Chain::Base))]
+    #[case(80_002, ChainNetwork::testnet(Chain::Polygon))]
+    #[case(1, ChainNetwork::mainnet(Chain::Ethereum))]
+    fn test_chain_network_chain_id(#[case] id: u64, #[case] network: ChainNetwork) {
+        assert_eq!(ChainNetwork::from_chain_id(id), Some(network));
+        assert_eq!(network.chain_id(), Some(id));
+        assert_eq!(network.is_testnet(), id != 1);
+    }
+
+    #[rstest::rstest]
+    #[case(Chain::Ethereum, ChainLayer::L1)]
+    #[case(Chain::Solana, ChainLayer::L1)]
+    #[case(Chain::Polygon, ChainLayer::L1)]
+    #[case(Chain::Starknet, ChainLayer::L2)]
+    #[case(Chain::Base, ChainLayer::L2)]
+    #[case(Chain::ZkSync, ChainLayer::L2)]
+    fn test_chain_layer(#[case] chain: Chain, #[case] layer: ChainLayer) {
+        assert_eq!(chain.layer(), layer);
+        assert_eq!(chain.is_rollup(), layer == ChainLayer::L2);
+    }
+
+    // Gas tokens are tracked through their wrapped or system contract address,
+    // e.g WETH on Ethereum, so each of them must have an address on its chain.
+    #[rstest::rstest]
+    #[case(Chain::Starknet)]
+    #[case(Chain::Solana)]
+    #[case(Chain::Sui)]
+    #[case(Chain::Aptos)]
+    #[case(Chain::Ethereum)]
+    #[case(Chain::Base)]
+    #[case(Chain::Arbitrum)]
+    #[case(Chain::Optimism)]
+    #[case(Chain::ZkSync)]
+    #[case(Chain::Polygon)]
+    #[case(Chain::Bnb)]
+    #[case(Chain::Avalanche)]
+    #[case(Chain::Gnosis)]
+    #[case(Chain::Worldchain)]
+    fn test_gas_token_has_address(#[case] chain: Chain) {
+        let gas_token = chain.gas_token();
+        assert!(gas_token.is_gas_token_of(chain));
+        assert!(gas_token.address(chain).is_some(), "{chain}");
+    }
+
+    #[rstest::rstest]
+    #[case(Chain::Starknet, VmFamily::Cairo)]
+    #[case(Chain::Solana, VmFamily::Svm)]
+    #[case(Chain::Sui, VmFamily::Move)]
+    #[case(Chain::Aptos, VmFamily::Move)]
+    #[case(Chain::Ethereum, VmFamily::Evm)]
+    #[case(Chain::Base, VmFamily::Evm)]
+    #[case(Chain::Arbitrum, VmFamily::Evm)]
+    #[case(Chain::Optimism, VmFamily::Evm)]
+    #[case(Chain::ZkSync, VmFamily::Evm)]
+    #[case(Chain::Polygon, VmFamily::Evm)]
+    #[case(Chain::Bnb, VmFamily::Evm)]
+    #[case(Chain::Avalanche, VmFamily::Evm)]
+    #[case(Chain::Gnosis, VmFamily::Evm)]
+    #[case(Chain::Worldchain, VmFamily::Evm)]
+    fn test_vm_family(#[case] chain: Chain, #[case] family: VmFamily) {
+        assert_eq!(chain.vm_family(), family);
+        assert_eq!(chain.is_evm(), family == VmFamily::Evm);
+        assert_eq!(chain.is_svm(), family == VmFamily::Svm);
+        assert_eq!(chain.is_move(), family == VmFamily::Move);
+        assert_eq!(chain.is_cairo(), family == VmFamily::Cairo);
+    }
+
+    #[test]
+    fn test_stablecoins() {
+        let tickers = |chain: Chain| -> Vec<String> {
+            chain
+                .stablecoins()
+                .into_iter()
+                .map(|token| token.ticker)
+                .collect()
+        };
+        assert_eq!(tickers(Chain::Ethereum), vec!["USDT", "USDC"]);
+        assert_eq!(tickers(Chain::Solana), vec!["USDC"]);
+
+        for chain in [Chain::Polygon, Chain::Gnosis, Chain::Sui, Chain::Worldchain] {
+            assert!(chain.stablecoins().contains(&chain.usd_token()), "{chain}");
+        }
+    }
+
+    #[test]
+    fn test_chain_network_without_chain_id() {
+        assert_eq!(ChainNetwork::from_chain_id(123_456_789), None);
+        assert_eq!(ChainNetwork::testnet(Chain::Starknet).chain_id(), None);
+        assert_eq!(ChainNetwork::from(Chain::Solana).chain_id(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case("\"ethereum\"", Chain::Ethereum)]
+    #[case("\"Ethereum\"", Chain::Ethereum)]
+    #[case("\"ETHEREUM\"", Chain::Ethereum)]
+    #[case("\"zksync\"", Chain::ZkSync)]
+    #[case("\"ZkSync\"", Chain::ZkSync)]
+    fn test_deserialize_case_insensitive(#[case] input: &str, #[case] expected: Chain) {
+        let chain: Chain = serde_json::from_str(input).unwrap();
+        assert_eq!(chain, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case("\"ethereum\"", Chain::Ethereum)]
+    #[case("1", Chain::Ethereum)]
+    #[case("8453", Chain::Base)]
+    fn test_deserialize_name_or_chain_id(#[case] input: &str, #[case] expected: Chain) {
+        let chain: Chain = serde_json::from_str(input).unwrap();
+        assert_eq!(chain, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_unknown_chain_id() {
+        let err = serde_json::from_str::<Chain>("123456789").unwrap_err();
+        assert!(
+            err.to_string().contains("Unknown chain id: 123456789"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let json = serde_json::to_string(&Chain::ZkSync).unwrap();
+        assert_eq!(json, "\"zksync\"");
+        assert_eq!(serde_json::from_str::<Chain>(&json).unwrap(), Chain::ZkSync);
+    }
+}
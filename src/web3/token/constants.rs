@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::OnceLock;
 
 use crate::web3::Chain;
@@ -20,6 +20,30 @@ static AAVE_LOCK: OnceLock<Token> = OnceLock::new();
 static BTC_LOCK: OnceLock<Token> = OnceLock::new();
 static JLP_LOCK: OnceLock<Token> = OnceLock::new();
 static WSTETH_LOCK: OnceLock<Token> = OnceLock::new();
+static TOKENS_BY_ADDRESS_LOCK: OnceLock<HashMap<(Chain, String), Token>> = OnceLock::new();
+
+/// Every token constant defined in this module.
+pub(crate) const KNOWN_TOKENS: [fn() -> Token; 15] = [
+    ETH, SOL, SUI, APT, POL, BNB, AVAX, XDAI, WLD, USDT, USDC, AAVE, BTC, JLP, WSTETH,
+];
+
+/// Resolves a known `Token` from its address on `chain`.
+///
+/// Addresses are compared case-insensitively.
+pub fn token_by_address(chain: Chain, address: &str) -> Option<Token> {
+    TOKENS_BY_ADDRESS_LOCK
+        .get_or_init(|| {
+            let mut index = HashMap::new();
+            for token in KNOWN_TOKENS.map(|token| token()) {
+                for (chain, address) in token.addresses.iter().flatten() {
+                    index.insert((*chain, address.to_lowercase()), token.clone());
+                }
+            }
+            index
+        })
+        .get(&(chain, address.to_lowercase()))
+        .cloned()
+}
 
 #[allow(non_snake_case)]
 #[must_use]
@@ -350,3 +374,39 @@ pub fn WSTETH() -> Token {
         })
         .clone()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_by_address() {
+        let token = token_by_address(
+            Chain::Ethereum,
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+        );
+        assert_eq!(token, Some(USDC()));
+
+        let token = token_by_address(
+            Chain::Ethereum,
+            "0xA0B86991C6218B36C1D19D4A2E9EB0CE3606EB48",
+        );
+        assert_eq!(token, Some(USDC()));
+    }
+
+    #[test]
+    fn test_token_by_address_unknown() {
+        assert_eq!(
+            token_by_address(
+                Chain::Ethereum,
+                "0x0000000000000000000000000000000000000001"
+            ),
+            None
+        );
+        // Known address, but not on this chain
+        assert_eq!(
+            token_by_address(Chain::Solana, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            None
+        );
+    }
+}
@@ -6,6 +6,9 @@ use std::collections::BTreeMap;
 
 use super::Chain;
 
+/// Tickers of the known USD stablecoins.
+pub const STABLECOIN_TICKERS: [&str; 6] = ["USDT", "USDC", "DAI", "FDUSD", "TUSD", "USDE"];
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize,))]
 #[cfg_attr(
@@ -54,4 +57,36 @@ impl Token {
     pub fn address(&self, chain: Chain) -> Option<String> {
         self.addresses.as_ref().and_then(|e| e.get(&chain).cloned())
     }
+
+    /// Returns true if the token is the gas token of `chain`
+    pub fn is_gas_token_of(&self, chain: Chain) -> bool {
+        *self == chain.gas_token()
+    }
+
+    /// Returns true if the ticker is one of the [`STABLECOIN_TICKERS`], ignoring case.
+    pub fn is_stablecoin(&self) -> bool {
+        STABLECOIN_TICKERS
+            .iter()
+            .any(|ticker| self.ticker.eq_ignore_ascii_case(ticker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stablecoin() {
+        assert!(USDC().is_stablecoin());
+        assert!(Token::new_without_addresses("Ethena USDe", "USDe", 18).is_stablecoin());
+        assert!(!ETH().is_stablecoin());
+    }
+
+    #[test]
+    fn test_is_gas_token_of() {
+        assert!(ETH().is_gas_token_of(Chain::Starknet));
+        assert!(POL().is_gas_token_of(Chain::Polygon));
+        assert!(!ETH().is_gas_token_of(Chain::Solana));
+        assert!(!USDC().is_gas_token_of(Chain::Ethereum));
+    }
 }
@@ -0,0 +1,264 @@
+//! Borsh layout stability tests.
+//!
+//! Entries are persisted borsh-encoded, and borsh encodes fields in declaration order.
+//! Each entry is encoded from a fixed fixture and compared against committed bytes, so
+//! any added, removed, reordered or retyped field fails here. New fields are appended,
+//! and the previous layout is kept in `entries::legacy` so stored bytes can be migrated.
+#[cfg(feature = "borsh")]
+mod test_borsh {
+    use pragma_common::{
+        entries::{
+            legacy::{GlobalExposureEntryV1, OpenInterestEntryV1, PriceEntryV1},
+            FundingRateEntry, GlobalExposureEntry, OpenInterestEntry, OrderbookData,
+            OrderbookEntry, OrderbookUpdateType, PositionEntry, PriceEntry, TradeEntry, TradeSide,
+            UpdateType, VolumeEntry,
+        },
+        web3::Chain,
+        Contract, InstrumentType, Pair,
+    };
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn assert_layout<T>(entry: &T, expected_hex: &str)
+    where
+        T: borsh::BorshSerialize + borsh::BorshDeserialize + PartialEq + std::fmt::Debug,
+    {
+        let bytes = borsh::to_vec(entry).unwrap();
+        assert_eq!(to_hex(&bytes), expected_hex);
+        assert_eq!(&borsh::from_slice::<T>(&bytes).unwrap(), entry);
+    }
+
+    fn pair() -> Pair {
+        Pair::from_currencies("BTC", "USD")
+    }
+
+    #[test]
+    fn test_price_entry_layout() {
+        let entry = PriceEntry {
+            source: "TEST".to_string(),
+            chain: Some(Chain::Starknet),
+            pair: pair(),
+            timestamp_ms: 1_700_000_000_000,
+            price: 12_000,
+            volume: 42,
+            expiration_timestamp: None,
+            instrument_type: InstrumentType::Perp,
+            received_timestamp_ms: 1_700_000_000_001,
+            decimals: None,
+        };
+        assert_layout(&entry, "0400000054455354010003000000425443030000005553440068e5cf8b010000e02e00000000000000000000000000002a00000000000000000000000000000000010168e5cf8b01000000");
+    }
+
+    #[test]
+    fn test_price_entry_decimals_layout() {
+        let entry = PriceEntry {
+            source: "TEST".to_string(),
+            chain: Some(Chain::Starknet),
+            pair: pair(),
+            timestamp_ms: 1_700_000_000_000,
+            price: 12_000,
+            volume: 42,
+            expiration_timestamp: None,
+            instrument_type: InstrumentType::Perp,
+            received_timestamp_ms: 1_700_000_000_001,
+            decimals: Some(2),
+        };
+        assert_layout(&entry, "0400000054455354010003000000425443030000005553440068e5cf8b010000e02e00000000000000000000000000002a00000000000000000000000000000000010168e5cf8b0100000102000000");
+    }
+
+    // Entries stored before `decimals` was appended
+    #[test]
+    fn test_price_entry_v1_migration() {
+        let bytes = from_hex("0400000054455354010003000000425443030000005553440068e5cf8b010000e02e00000000000000000000000000002a00000000000000000000000000000000010168e5cf8b010000");
+        let entry = PriceEntry::from(borsh::from_slice::<PriceEntryV1>(&bytes).unwrap());
+        assert_eq!(
+            entry,
+            PriceEntry {
+                source: "TEST".to_string(),
+                chain: Some(Chain::Starknet),
+                pair: pair(),
+                timestamp_ms: 1_700_000_000_000,
+                price: 12_000,
+                volume: 42,
+                expiration_timestamp: None,
+                instrument_type: InstrumentType::Perp,
+                received_timestamp_ms: 1_700_000_000_001,
+                decimals: None,
+            }
+        );
+        assert!(borsh::from_slice::<PriceEntry>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_orderbook_data_layout() {
+        let data = OrderbookData {
+            update_id: 42,
+            bids: vec![(99.5, 1.0)],
+            asks: vec![(100.5, 2.0)],
+        };
+        assert_layout(&data, "2a00000000000000010000000000000000e05840000000000000f03f0100000000000000002059400000000000000040");
+    }
+
+    #[test]
+    fn test_orderbook_entry_layout() {
+        let entry = OrderbookEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Spot,
+            pair: pair(),
+            r#type: OrderbookUpdateType::Update(UpdateType::Delta),
+            data: OrderbookData {
+                update_id: 42,
+                bids: vec![(99.5, 1.0)],
+                asks: vec![],
+            },
+            timestamp_ms: 1_700_000_000_000,
+            received_timestamp_ms: 1_700_000_000_001,
+        };
+        assert_layout(&entry, "040000005445535400030000004254430300000055534400012a00000000000000010000000000000000e05840000000000000f03f000000000068e5cf8b0100000168e5cf8b010000");
+    }
+
+    #[test]
+    fn test_funding_rate_entry_layout() {
+        let entry = FundingRateEntry {
+            source: "TEST".to_string(),
+            pair: pair(),
+            annualized_rate: 0.1,
+            timestamp_ms: 1_700_000_000_000,
+            instrument_type: InstrumentType::Perp,
+            received_timestamp_ms: 1_700_000_000_001,
+        };
+        assert_layout(&entry, "040000005445535403000000425443030000005553449a9999999999b93f0068e5cf8b010000010168e5cf8b010000");
+    }
+
+    #[test]
+    fn test_open_interest_entry_layout() {
+        let entry = OpenInterestEntry {
+            source: "TEST".to_string(),
+            pair: pair(),
+            open_interest: 1_000.0,
+            timestamp_ms: 1_700_000_000_000,
+            instrument_type: InstrumentType::Perp,
+            chain: None,
+            received_timestamp_ms: 1_700_000_000_001,
+        };
+        assert_layout(&entry, "040000005445535403000000425443030000005553440000000000408f400068e5cf8b010000010168e5cf8b01000000");
+    }
+
+    // Entries stored before `chain` was appended
+    #[test]
+    fn test_open_interest_entry_v1_migration() {
+        let bytes = from_hex(
+            "040000005445535403000000425443030000005553440000000000408f400068e5cf8b010000010168e5cf8b010000",
+        );
+        let entry =
+            OpenInterestEntry::from(borsh::from_slice::<OpenInterestEntryV1>(&bytes).unwrap());
+        assert_eq!(
+            entry,
+            OpenInterestEntry {
+                source: "TEST".to_string(),
+                pair: pair(),
+                open_interest: 1_000.0,
+                timestamp_ms: 1_700_000_000_000,
+                instrument_type: InstrumentType::Perp,
+                received_timestamp_ms: 1_700_000_000_001,
+                chain: None,
+            }
+        );
+        assert!(borsh::from_slice::<OpenInterestEntry>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_volume_entry_layout() {
+        let entry = VolumeEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Spot,
+            pair: pair(),
+            volume_daily: 5_000.0,
+            timestamp_ms: 1_700_000_000_000,
+            received_timestamp_ms: 1_700_000_000_001,
+        };
+        assert_layout(&entry, "0400000054455354000300000042544303000000555344000000000088b3400068e5cf8b0100000168e5cf8b010000");
+    }
+
+    #[test]
+    fn test_trade_entry_layout() {
+        let entry = TradeEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Spot,
+            pair: pair(),
+            trade_id: "1".to_string(),
+            buyer_address: "0xa".to_string(),
+            seller_address: "0xb".to_string(),
+            side: TradeSide::Sell,
+            size: 1.5,
+            price: 100.0,
+            timestamp_ms: 1_700_000_000_000,
+            received_timestamp_ms: 1_700_000_000_001,
+        };
+        assert_layout(&entry, "04000000544553540003000000425443030000005553440100000031030000003078610300000030786201000000000000f83f00000000000059400068e5cf8b0100000168e5cf8b010000");
+    }
+
+    #[test]
+    fn test_position_entry_layout() {
+        let entry = PositionEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Perp,
+            pair: Pair::from_currencies("WTI", "USD"),
+            timestamp_ms: 1_700_000_000_000,
+            received_timestamp_ms: 1_700_000_000_001,
+            side: TradeSide::Buy,
+            size: 10.0,
+            contract: Some(Contract::from_cme_symbol("CLK6").unwrap()),
+        };
+        assert_layout(&entry, "04000000544553540103000000575449030000005553440068e5cf8b0100000168e5cf8b010000000000000000002440010002434c000000000000000000000000000004ea07");
+    }
+
+    #[test]
+    fn test_global_exposure_entry_layout() {
+        let entry = GlobalExposureEntry {
+            source: "TEST".to_string(),
+            timestamp_ms: 1_700_000_000_000,
+            asset: "ETH".to_string(),
+            gross_position_size: 3.0,
+            net_position_size: -1.0,
+            contract: None,
+            chain: Some(Chain::Arbitrum),
+        };
+        assert_layout(
+            &entry,
+            "04000000544553540068e5cf8b010000030000004554480000000000000840000000000000f0bf000106",
+        );
+    }
+
+    // Entries stored before `chain` was appended
+    #[test]
+    fn test_global_exposure_entry_v1_migration() {
+        let bytes = from_hex(
+            "04000000544553540068e5cf8b010000030000004554480000000000000840000000000000f0bf00",
+        );
+        let entry =
+            GlobalExposureEntry::from(borsh::from_slice::<GlobalExposureEntryV1>(&bytes).unwrap());
+        assert_eq!(
+            entry,
+            GlobalExposureEntry {
+                source: "TEST".to_string(),
+                timestamp_ms: 1_700_000_000_000,
+                asset: "ETH".to_string(),
+                gross_position_size: 3.0,
+                net_position_size: -1.0,
+                contract: None,
+                chain: None,
+            }
+        );
+        assert!(borsh::from_slice::<GlobalExposureEntry>(&bytes).is_err());
+    }
+}
@@ -1,3 +1,9 @@
+#[cfg(feature = "starknet")]
+mod common;
+
+#[cfg(feature = "starknet")]
+use common::CapturedLogs;
+
 #[cfg(feature = "starknet")]
 #[tokio::test]
 async fn test_fallback() {
@@ -18,3 +24,236 @@ async fn test_fallback() {
     let chain_id = provider.chain_id().await.unwrap();
     assert_eq!(chain_id, felt_hex!("0x534e5f4d41494e"))
 }
+
+#[cfg(all(feature = "starknet", feature = "services"))]
+#[tokio::test]
+async fn test_wait_for_with_cancel() {
+    use std::time::{Duration, Instant};
+
+    use pragma_common::services::ServiceContext;
+    use pragma_common::starknet::{
+        fallback_provider::{FallbackProvider, WaitForTarget},
+        WaitForError,
+    };
+    use starknet_rust::{macros::felt_hex, providers::Url};
+
+    // Connections are queued by the OS but never answered, so polling hangs
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+    let provider = FallbackProvider::new(vec![url]).unwrap();
+
+    let ctx = ServiceContext::new();
+    let cancel = ctx.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        cancel.cancel();
+    });
+
+    let started = Instant::now();
+    let result = provider
+        .wait_for_with_cancel(
+            felt_hex!("0x1234"),
+            WaitForTarget::AcceptedOnL2,
+            Some(Duration::from_millis(10)),
+            Some(Duration::from_secs(60)),
+            &ctx,
+        )
+        .await;
+
+    assert!(matches!(result, Err(WaitForError::Cancelled)), "{result:?}");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+/// Serves `response` as the JSON-RPC body of every request, returns the server URL
+/// and the number of requests received.
+#[cfg(feature = "starknet")]
+async fn mock_rpc(
+    response: &'static str,
+) -> (
+    starknet_rust::providers::Url,
+    std::sync::Arc<std::sync::atomic::AtomicUsize>,
+) {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url =
+        starknet_rust::providers::Url::parse(&format!("http://{}", listener.local_addr().unwrap()))
+            .unwrap();
+    let hits = Arc::new(AtomicUsize::new(0));
+
+    let server_hits = hits.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            server_hits.fetch_add(1, Ordering::Relaxed);
+
+            // Read the headers then the body, so the client sees a complete exchange
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let header_end = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let headers = String::from_utf8_lossy(&request[..header_end]).to_lowercase();
+            let content_length: usize = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|value| value.trim().parse().unwrap())
+                .unwrap_or(0);
+            while request.len() < header_end + content_length {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+
+            let reply = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            );
+            stream.write_all(reply.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+        }
+    });
+
+    (url, hits)
+}
+
+#[cfg(feature = "starknet")]
+#[tokio::test]
+async fn test_fallback_to_secondary_on_transient_error() {
+    use std::sync::atomic::Ordering;
+
+    use pragma_common::starknet::fallback_provider::FallbackProvider;
+    use starknet_rust::{macros::felt_hex, providers::Provider};
+
+    let (primary, primary_hits) = mock_rpc(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32099,"message":"Unable to complete request at this time."}}"#,
+    )
+    .await;
+    let (secondary, secondary_hits) =
+        mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x534e5f5345504f4c4941"}"#).await;
+
+    let provider = FallbackProvider::new(vec![primary, secondary]).unwrap();
+
+    let chain_id = provider.chain_id().await.unwrap();
+    assert_eq!(chain_id, felt_hex!("0x534e5f5345504f4c4941"));
+    assert_eq!(primary_hits.load(Ordering::Relaxed), 1);
+    assert_eq!(secondary_hits.load(Ordering::Relaxed), 1);
+}
+
+#[cfg(feature = "starknet")]
+#[tokio::test]
+async fn test_fallback_returns_last_error_when_all_fail() {
+    use pragma_common::starknet::fallback_provider::FallbackProvider;
+    use starknet_rust::providers::Provider;
+
+    let (primary, _) = mock_rpc(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32099,"message":"Unable to complete request at this time. (primary)"}}"#,
+    )
+    .await;
+    let (secondary, _) = mock_rpc(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32099,"message":"Unable to complete request at this time. (secondary)"}}"#,
+    )
+    .await;
+
+    let provider = FallbackProvider::new(vec![primary, secondary]).unwrap();
+
+    let error = provider.chain_id().await.unwrap_err();
+    assert!(error.to_string().contains("(secondary)"), "{error}");
+}
+
+#[cfg(feature = "starknet")]
+#[tokio::test]
+async fn test_fallback_logs_failover() {
+    use pragma_common::starknet::fallback_provider::FallbackProvider;
+    use starknet_rust::providers::Provider;
+
+    let logs = CapturedLogs::start();
+
+    let (primary, _) = mock_rpc(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32099,"message":"Unable to complete request at this time."}}"#,
+    )
+    .await;
+    let (secondary, _) =
+        mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x534e5f5345504f4c4941"}"#).await;
+
+    let provider = FallbackProvider::new(vec![primary, secondary])
+        .unwrap()
+        .with_sticky_failover(true);
+    provider.chain_id().await.unwrap();
+
+    let logs = logs.contents();
+    assert!(
+        logs.contains("WARN") && logs.contains("falling back to the next one"),
+        "{logs}"
+    );
+    assert!(logs.contains("provider_index=0"), "{logs}");
+    assert!(logs.contains("error_kind=\"other\""), "{logs}");
+    assert!(
+        logs.contains("Sticky failover switched the active RPC provider"),
+        "{logs}"
+    );
+}
+
+#[cfg(feature = "starknet")]
+#[tokio::test]
+async fn test_failover_log_redacts_api_key() {
+    use pragma_common::starknet::fallback_provider::FallbackProvider;
+    use starknet_rust::providers::{Provider, Url};
+
+    let logs = CapturedLogs::start();
+
+    // Nothing listens on the primary anymore, so reqwest fails to send the request
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let primary = Url::parse(&format!(
+        "http://{}/rpc?apikey=secret",
+        closed.local_addr().unwrap()
+    ))
+    .unwrap();
+    drop(closed);
+    let (secondary, _) =
+        mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x534e5f5345504f4c4941"}"#).await;
+
+    let provider = FallbackProvider::new(vec![primary, secondary]).unwrap();
+    provider.chain_id().await.unwrap();
+
+    let logs = logs.contents();
+    assert!(logs.contains("falling back to the next one"), "{logs}");
+    assert!(logs.contains("endpoint=http://127.0.0.1:"), "{logs}");
+    assert!(!logs.contains("secret"), "{logs}");
+}
+
+#[cfg(feature = "starknet")]
+#[tokio::test]
+async fn test_load_balance_does_not_log_sticky_switch() {
+    use pragma_common::starknet::fallback_provider::FallbackProvider;
+    use starknet_rust::providers::Provider;
+
+    let logs = CapturedLogs::start();
+
+    let (primary, _) =
+        mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x534e5f5345504f4c4941"}"#).await;
+    let (secondary, _) =
+        mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x534e5f5345504f4c4941"}"#).await;
+
+    let provider = FallbackProvider::new(vec![primary, secondary])
+        .unwrap()
+        .with_sticky_failover(true)
+        .with_load_balance(true);
+    for _ in 0..4 {
+        provider.chain_id().await.unwrap();
+    }
+
+    let logs = logs.contents();
+    assert!(
+        !logs.contains("Sticky failover switched the active RPC provider"),
+        "{logs}"
+    );
+    assert_eq!(provider.current_provider_index().await, 0);
+}
@@ -9,7 +9,7 @@ use pragma_common::{
     entries::volume::VolumeEntry,
     instrument_type::InstrumentType,
     web3::Chain,
-    Contract, Pair, ProtoDeserialize, ProtoSerialize,
+    Contract, DecodeLimits, Pair, ProtoDeserialize, ProtoSerialize,
 };
 
 #[cfg(feature = "proto")]
@@ -25,6 +25,7 @@ fn test_price_entry_proto() {
         expiration_timestamp: Some(0),
         received_timestamp_ms: 145577,
         instrument_type: InstrumentType::Perp,
+        decimals: Some(8),
     };
     let payload = x.to_proto_bytes();
     let entry: PriceEntry = PriceEntry::from_proto_bytes(&payload).unwrap();
@@ -32,6 +33,28 @@ fn test_price_entry_proto() {
     assert_eq!(entry.instrument_type, InstrumentType::Perp);
 }
 
+#[cfg(feature = "proto")]
+#[test]
+fn test_price_entry_proto_rejects_zero_timestamp() {
+    let x = PriceEntry {
+        source: "TEST".to_string(),
+        chain: None,
+        pair: Pair::from_currencies("BTC", "USD"),
+        timestamp_ms: 0,
+        price: 1000,
+        volume: 0,
+        expiration_timestamp: None,
+        instrument_type: InstrumentType::Spot,
+        received_timestamp_ms: 1,
+        decimals: None,
+    };
+
+    let payload = x.to_proto_bytes();
+    let err = PriceEntry::from_proto_bytes(&payload).unwrap_err();
+
+    assert!(err.to_string().contains("Invalid timestamp_ms value: 0"));
+}
+
 #[cfg(feature = "proto")]
 #[test]
 fn test_orderbook_update_proto() {
@@ -53,6 +76,49 @@ fn test_orderbook_update_proto() {
     assert_eq!(orderbook_update, x);
 }
 
+#[cfg(feature = "proto")]
+#[test]
+fn test_orderbook_proto_limited() {
+    let x = OrderbookEntry {
+        source: "TEST".to_string(),
+        instrument_type: InstrumentType::Spot,
+        pair: Pair::from_currencies("BTC", "USD"),
+        r#type: OrderbookUpdateType::Snapshot,
+        data: OrderbookData {
+            update_id: 1,
+            bids: (1..=5).map(|i| (f64::from(i), 1.0)).collect(),
+            asks: vec![(10.0, 1.0)],
+        },
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+    };
+    let payload = x.to_proto_bytes();
+
+    let limits = DecodeLimits {
+        max_levels: 5,
+        ..Default::default()
+    };
+    assert_eq!(
+        OrderbookEntry::from_proto_bytes_limited(&payload, limits).unwrap(),
+        x
+    );
+
+    let limits = DecodeLimits {
+        max_levels: 4,
+        ..Default::default()
+    };
+    let err = OrderbookEntry::from_proto_bytes_limited(&payload, limits).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("5 bids exceed the limit of 4 levels"));
+
+    let limits = DecodeLimits {
+        max_message_size: payload.len() - 1,
+        ..Default::default()
+    };
+    assert!(OrderbookEntry::from_proto_bytes_limited(&payload, limits).is_err());
+}
+
 #[cfg(feature = "proto")]
 #[test]
 fn test_orderbook_snapshot_proto() {
@@ -121,6 +187,20 @@ fn test_open_interest_entry_proto() {
         timestamp_ms: 145567,
         received_timestamp_ms: 145577,
         instrument_type: InstrumentType::Perp,
+        chain: None,
+    };
+    let payload = x.to_proto_bytes();
+    let entry: OpenInterestEntry = OpenInterestEntry::from_proto_bytes(&payload).unwrap();
+    assert_eq!(entry, x);
+
+    let x = OpenInterestEntry {
+        source: "TEST".to_string(),
+        pair: Pair::from_currencies("ETH", "USD"),
+        open_interest: 2500.0,
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+        instrument_type: InstrumentType::Spot,
+        chain: Some(Chain::Starknet),
     };
     let payload = x.to_proto_bytes();
     let entry: OpenInterestEntry = OpenInterestEntry::from_proto_bytes(&payload).unwrap();
@@ -143,6 +223,31 @@ fn test_volume_entry_proto() {
     assert_eq!(entry, x);
 }
 
+#[cfg(feature = "proto")]
+#[test]
+fn test_position_entry_proto() {
+    // Re-exported from `entries` alongside `TradeSide`
+    use pragma_common::entries::{PositionEntry, TradeSide};
+
+    for side in [TradeSide::Buy, TradeSide::Sell] {
+        let x = PositionEntry {
+            source: "TEST".to_string(),
+            instrument_type: InstrumentType::Perp,
+            pair: Pair::from_currencies("BTC", "USD"),
+            timestamp_ms: 145567,
+            received_timestamp_ms: 145577,
+            side,
+            size: 0.5,
+            contract: None,
+        };
+
+        let payload = x.to_proto_bytes();
+        let entry: PositionEntry = PositionEntry::from_proto_bytes(&payload).unwrap();
+
+        assert_eq!(entry, x);
+    }
+}
+
 #[cfg(feature = "proto")]
 #[test]
 fn test_position_entry_proto_with_contract() {
@@ -206,3 +311,122 @@ fn test_trade_entry_proto() {
 
     assert_eq!(entry, x);
 }
+
+#[cfg(feature = "proto")]
+#[test]
+fn test_into_proto_bytes_matches_to_proto_bytes() {
+    use pragma_common::entries::{global_exposure::GlobalExposureEntry, trade::TradeEntry};
+
+    let price = PriceEntry {
+        source: "TEST".to_string(),
+        chain: Some(Chain::Starknet),
+        pair: Pair::from_currencies("BTC", "USD"),
+        timestamp_ms: 145567,
+        price: 12000,
+        volume: 42,
+        expiration_timestamp: None,
+        received_timestamp_ms: 145577,
+        decimals: None,
+        instrument_type: InstrumentType::Spot,
+    };
+    assert_eq!(price.to_proto_bytes(), price.clone().into_proto_bytes());
+
+    let orderbook = OrderbookEntry {
+        source: "TEST".to_string(),
+        instrument_type: InstrumentType::Perp,
+        pair: Pair::from_currencies("ETH", "USD"),
+        r#type: OrderbookUpdateType::Update(UpdateType::Delta),
+        data: OrderbookData {
+            update_id: 4242,
+            bids: vec![(41.0, 1.0), (40.0, 2.0)],
+            asks: vec![(42.0, 3.0)],
+        },
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+    };
+    assert_eq!(
+        orderbook.to_proto_bytes(),
+        orderbook.clone().into_proto_bytes()
+    );
+
+    let position = PositionEntry {
+        source: "TEST".to_string(),
+        instrument_type: InstrumentType::Perp,
+        pair: Pair::from_currencies("WTI", "USD"),
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+        side: TradeSide::Sell,
+        size: 10.0,
+        contract: Some(Contract::from_cme_symbol("CLK6").unwrap()),
+    };
+    assert_eq!(
+        position.to_proto_bytes(),
+        position.clone().into_proto_bytes()
+    );
+
+    let trade = TradeEntry {
+        source: "TEST".to_string(),
+        instrument_type: InstrumentType::Spot,
+        pair: Pair::from_currencies("BTC", "USD"),
+        trade_id: "0x4567576".into(),
+        buyer_address: "0x1234567890".into(),
+        seller_address: "0xabcdef1234567890".into(),
+        side: TradeSide::Buy,
+        size: 1.0,
+        price: 101_024.0,
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+    };
+    assert_eq!(trade.to_proto_bytes(), trade.clone().into_proto_bytes());
+
+    let volume = VolumeEntry {
+        source: "TEST".to_string(),
+        instrument_type: InstrumentType::Spot,
+        pair: Pair::from_currencies("ETH", "USD"),
+        volume_daily: 5000.0,
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+    };
+    assert_eq!(volume.to_proto_bytes(), volume.clone().into_proto_bytes());
+
+    let funding_rate = FundingRateEntry {
+        source: "TEST".to_string(),
+        pair: Pair::from_currencies("BTC", "USD"),
+        annualized_rate: 42.42,
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+        instrument_type: InstrumentType::Perp,
+    };
+    assert_eq!(
+        funding_rate.to_proto_bytes(),
+        funding_rate.clone().into_proto_bytes()
+    );
+
+    let open_interest = OpenInterestEntry {
+        source: "TEST".to_string(),
+        pair: Pair::from_currencies("ETH", "USD"),
+        open_interest: 2500.0,
+        timestamp_ms: 145567,
+        received_timestamp_ms: 145577,
+        instrument_type: InstrumentType::Perp,
+        chain: Some(Chain::Starknet),
+    };
+    assert_eq!(
+        open_interest.to_proto_bytes(),
+        open_interest.clone().into_proto_bytes()
+    );
+
+    let global_exposure = GlobalExposureEntry {
+        source: "TEST".to_string(),
+        timestamp_ms: 145567,
+        asset: "ETH".to_string(),
+        gross_position_size: 3.0,
+        net_position_size: -1.0,
+        contract: Some(Contract::from_cme_symbol("CLK6").unwrap()),
+        chain: Some(Chain::Arbitrum),
+    };
+    assert_eq!(
+        global_exposure.to_proto_bytes(),
+        global_exposure.clone().into_proto_bytes()
+    );
+}
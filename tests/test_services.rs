@@ -1,6 +1,12 @@
+#[cfg(feature = "services")]
+mod common;
+
 #[cfg(feature = "services")]
 mod test_services {
-    use pragma_common::services::{Service, ServiceContext, ServiceGroup, ServiceRunner};
+    use super::common::CapturedLogs;
+    use pragma_common::services::{
+        FnService, Service, ServiceContext, ServiceGroup, ServiceGroupStats, ServiceRunner,
+    };
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
     use tokio::task::JoinSet;
@@ -16,6 +22,30 @@ mod test_services {
         assert!(ctx.is_cancelled());
     }
 
+    #[tokio::test]
+    async fn test_service_context_cancelled() {
+        let ctx = ServiceContext::new();
+
+        // Not cancelled yet: the sleep wins
+        let cancelled = tokio::select! {
+            () = ctx.cancelled() => true,
+            () = sleep(Duration::from_millis(20)) => false,
+        };
+        assert!(!cancelled);
+
+        // Cancelled from another task while selecting
+        let ctx_clone = ctx.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(20)).await;
+            ctx_clone.cancel();
+        });
+        let cancelled = tokio::select! {
+            () = ctx.cancelled() => true,
+            () = sleep(Duration::from_secs(5)) => false,
+        };
+        assert!(cancelled);
+    }
+
     #[tokio::test]
     async fn test_service_context_run_until_cancelled() {
         let ctx = ServiceContext::new();
@@ -151,6 +181,50 @@ mod test_services {
         );
     }
 
+    #[tokio::test]
+    async fn test_service_runner_spawn_blocking_loop() {
+        let ctx = ServiceContext::new();
+        let mut join_set = JoinSet::new();
+        let mut runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+
+        let counter = Arc::new(Mutex::new(0));
+        let counter_for_task = counter.clone();
+
+        runner.spawn_blocking_loop(move |ctx| {
+            while !ctx.is_cancelled() {
+                {
+                    let mut locked = counter_for_task.lock().unwrap();
+                    *locked += 1;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Ok::<(), anyhow::Error>(())
+        });
+
+        // Let it run for a bit, then cancel
+        sleep(Duration::from_millis(100)).await;
+        ctx.cancel();
+
+        // The blocking loop should notice the cancellation well before the grace period
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while let Some(result) = join_set.join_next().await {
+                result.unwrap().unwrap();
+            }
+        })
+        .await
+        .expect("Blocking loop should stop on cancellation");
+
+        let count_after_cancel = *counter.lock().unwrap();
+        assert!(count_after_cancel > 0, "Blocking loop should have run");
+
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            count_after_cancel,
+            *counter.lock().unwrap(),
+            "Blocking loop should have stopped"
+        );
+    }
+
     #[tokio::test]
     async fn test_service_lifecycle() {
         let counter = Arc::new(Mutex::new(0));
@@ -267,6 +341,52 @@ mod test_services {
         assert_eq!(count2_before, count2_after, "Service 2 should have stopped");
     }
 
+    #[tokio::test]
+    async fn test_fn_service_in_group() {
+        let counter = Arc::new(Mutex::new(0));
+        let counter_for_task = counter.clone();
+
+        let mut group =
+            ServiceGroup::default().with_critical(FnService::new(move |ctx| async move {
+                while !ctx.is_cancelled() {
+                    {
+                        let mut locked = counter_for_task.lock().unwrap();
+                        *locked += 1;
+                    }
+                    sleep(Duration::from_millis(10)).await;
+                }
+                Ok(())
+            }));
+
+        let ctx = ServiceContext::new();
+        let mut join_set = JoinSet::new();
+        let runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+
+        group.start(runner).await.unwrap();
+
+        // Let the function run
+        sleep(Duration::from_millis(100)).await;
+        assert!(*counter.lock().unwrap() > 0, "FnService should have run");
+
+        // Cancel and wait for the group to stop
+        ctx.cancel();
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while let Some(result) = join_set.join_next().await {
+                result.unwrap().unwrap();
+            }
+        })
+        .await
+        .expect("FnService should stop on cancellation");
+
+        let count_before = *counter.lock().unwrap();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            count_before,
+            *counter.lock().unwrap(),
+            "FnService should have stopped"
+        );
+    }
+
     #[tokio::test]
     async fn test_empty_service_group() {
         let mut group = ServiceGroup::default();
@@ -427,6 +547,273 @@ mod test_services {
         assert_eq!(count1_before, count1_after, "Service 1 should have stopped");
     }
 
+    #[tokio::test]
+    async fn test_service_group_stats() {
+        let mut group = ServiceGroup::default()
+            .with_critical(TestService {
+                counter: Arc::new(Mutex::new(0)),
+                sleep_duration: Some(Duration::from_millis(10)),
+                should_panic: false,
+            })
+            .with_auxiliary(TestService {
+                counter: Arc::new(Mutex::new(0)),
+                sleep_duration: Some(Duration::from_millis(10)),
+                should_panic: true,
+            });
+
+        let ctx = ServiceContext::new();
+        let mut join_set = JoinSet::new();
+        let runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+
+        group.start(runner).await.unwrap();
+
+        // Let the auxiliary service panic
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            group.stats(),
+            ServiceGroupStats {
+                critical_total: 1,
+                auxiliary_total: 1,
+                failures: 1,
+            }
+        );
+        assert_eq!(group.running_count(), 1);
+
+        ctx.cancel();
+        while let Some(result) = join_set.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        assert_eq!(group.running_count(), 0);
+        assert_eq!(group.stats().failures, 1);
+    }
+
+    struct ShutdownCountingService {
+        shutdowns: Arc<Mutex<u32>>,
+        failure: Option<Failure>,
+    }
+
+    #[derive(Clone, Copy)]
+    enum Failure {
+        Error,
+        Panic,
+    }
+
+    #[async_trait::async_trait]
+    impl Service for ShutdownCountingService {
+        async fn start<'a>(&mut self, mut runner: ServiceRunner<'a>) -> anyhow::Result<()> {
+            let failure = self.failure;
+            runner.spawn_loop(move |ctx| async move {
+                match failure {
+                    Some(Failure::Error) => anyhow::bail!("critical service failed"),
+                    Some(Failure::Panic) => panic!("critical service exploded"),
+                    None => ctx.cancelled().await,
+                }
+                anyhow::Ok(())
+            });
+            Ok(())
+        }
+
+        async fn on_shutdown(&mut self) {
+            *self.shutdowns.lock().unwrap() += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_on_shutdown_runs_once_on_cancel() {
+        let critical_shutdowns = Arc::new(Mutex::new(0));
+        let auxiliary_shutdowns = Arc::new(Mutex::new(0));
+        let mut group = ServiceGroup::default()
+            .with_critical(ShutdownCountingService {
+                shutdowns: critical_shutdowns.clone(),
+                failure: None,
+            })
+            .with_auxiliary(ShutdownCountingService {
+                shutdowns: auxiliary_shutdowns.clone(),
+                failure: None,
+            });
+
+        let ctx = ServiceContext::new();
+        let mut join_set = JoinSet::new();
+        let runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+        group.start(runner).await.unwrap();
+
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(*critical_shutdowns.lock().unwrap(), 0);
+
+        ctx.cancel();
+        while let Some(result) = join_set.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        assert_eq!(*critical_shutdowns.lock().unwrap(), 1);
+        assert_eq!(*auxiliary_shutdowns.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_on_shutdown_runs_once_on_critical_failure() {
+        for failure in [Failure::Error, Failure::Panic] {
+            let shutdowns: Vec<_> = (0..2).map(|_| Arc::new(Mutex::new(0))).collect();
+            let mut group = ServiceGroup::default()
+                .with_critical(ShutdownCountingService {
+                    shutdowns: shutdowns[0].clone(),
+                    failure: Some(failure),
+                })
+                .with_critical(ShutdownCountingService {
+                    shutdowns: shutdowns[1].clone(),
+                    failure: None,
+                });
+
+            let ctx = ServiceContext::new();
+            let mut join_set = JoinSet::new();
+            let runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+            group.start(runner).await.unwrap();
+
+            // The group ends on its own, without being cancelled
+            let result = join_set.join_next().await.unwrap();
+            match failure {
+                Failure::Error => assert!(result.unwrap().is_err()),
+                Failure::Panic => assert!(result.unwrap_err().is_panic()),
+            }
+            assert!(!ctx.is_cancelled());
+            for shutdown in &shutdowns {
+                assert_eq!(*shutdown.lock().unwrap(), 1);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_and_drive_to_end_runs_on_shutdown() {
+        let shutdowns = Arc::new(Mutex::new(0));
+        let result = ShutdownCountingService {
+            shutdowns: shutdowns.clone(),
+            failure: Some(Failure::Error),
+        }
+        .start_and_drive_to_end()
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(*shutdowns.lock().unwrap(), 1);
+    }
+
+    struct SlowStartService;
+
+    #[async_trait::async_trait]
+    impl Service for SlowStartService {
+        async fn start<'a>(&mut self, mut runner: ServiceRunner<'a>) -> anyhow::Result<()> {
+            sleep(Duration::from_millis(200)).await;
+            runner.spawn_loop(|ctx| async move {
+                ctx.cancelled().await;
+                anyhow::Ok(())
+            });
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_concurrent() {
+        let mut group = ServiceGroup::default()
+            .with_critical(SlowStartService)
+            .with_critical(SlowStartService)
+            .with_auxiliary(SlowStartService)
+            .with_auxiliary(SlowStartService);
+
+        let ctx = ServiceContext::new();
+        let mut join_set = JoinSet::new();
+        let runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+
+        let started = tokio::time::Instant::now();
+        group.start_concurrent(runner).await.unwrap();
+        // Critical then auxiliary services start together: ~400ms instead of ~800ms
+        assert!(started.elapsed() < Duration::from_millis(600));
+        assert_eq!(group.running_count(), 4);
+
+        ctx.cancel();
+        while let Some(result) = join_set.join_next().await {
+            result.unwrap().unwrap();
+        }
+        assert_eq!(group.running_count(), 0);
+    }
+
+    struct FailingStartService;
+
+    #[async_trait::async_trait]
+    impl Service for FailingStartService {
+        async fn start<'a>(&mut self, _runner: ServiceRunner<'a>) -> anyhow::Result<()> {
+            anyhow::bail!("misconfigured auxiliary")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auxiliary_service_start_failure_is_logged() {
+        let logs = CapturedLogs::start();
+
+        let counter = Arc::new(Mutex::new(0));
+        let mut group = ServiceGroup::default()
+            .with_critical(TestService {
+                counter: counter.clone(),
+                sleep_duration: Some(Duration::from_millis(10)),
+                should_panic: false,
+            })
+            .with_auxiliary(FailingStartService);
+
+        let ctx = ServiceContext::new();
+        let mut join_set = JoinSet::new();
+        let runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+
+        // The group still starts, the auxiliary failure is only reported
+        group.start(runner).await.unwrap();
+
+        let logs = logs.contents();
+        assert!(logs.contains("WARN"), "Expected a warning, got: {logs}");
+        assert!(
+            logs.contains("misconfigured auxiliary"),
+            "Expected the start error in the warning, got: {logs}"
+        );
+
+        ctx.cancel();
+        while let Some(result) = join_set.join_next().await {
+            result.unwrap().unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auxiliary_service_panic_is_logged() {
+        let logs = CapturedLogs::start();
+
+        let mut group = ServiceGroup::default()
+            .with_critical(FnService::new(|ctx: ServiceContext| async move {
+                ctx.cancelled().await;
+                Ok(())
+            }))
+            .with_auxiliary(FnService::new(|_ctx: ServiceContext| async move {
+                panic!("auxiliary exploded");
+            }));
+
+        let ctx = ServiceContext::new();
+        let mut join_set = JoinSet::new();
+        let runner = ServiceRunner::new(ctx.clone(), &mut join_set);
+        group.start(runner).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // The critical service is still running
+        assert_eq!(group.running_count(), 1);
+        assert_eq!(group.stats().failures, 1);
+
+        let logs_str = logs.contents();
+        assert!(
+            logs_str.contains("Auxiliary service panicked: auxiliary exploded"),
+            "Expected the panic to be logged, got: {logs_str}"
+        );
+
+        ctx.cancel();
+        while let Some(result) = join_set.join_next().await {
+            result.unwrap().unwrap();
+        }
+    }
+
     #[tokio::test]
     #[should_panic(expected = "Service panic as requested")]
     async fn test_critical_service_failure() {